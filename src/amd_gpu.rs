@@ -0,0 +1,129 @@
+// amd_gpu.rs - AMD GPU monitoring through the amdgpu driver's sysfs interface
+
+use std::path::Path;
+
+use crate::gpu::{GpuInfo, GpuVendor};
+use crate::sysfs::{drm_cards, find_hwmon, read_attr, read_u64};
+
+// PCI vendor ID of AMD/ATI devices
+const AMD_PCI_VENDOR: &str = "0x1002";
+
+/// Read a single amdgpu device from its /sys/class/drm/cardN/device directory
+///
+/// Returns None if the device is not an AMD GPU.
+pub fn read_amd_gpu(card_name: &str, device_dir: &Path) -> Option<GpuInfo> {
+    if read_attr(&device_dir.join("vendor"))?.to_lowercase() != AMD_PCI_VENDOR {
+        return None;
+    }
+
+    // Newer kernels expose the marketing name; fall back to the PCI device ID
+    let name = read_attr(&device_dir.join("product_name"))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| {
+            let device_id = read_attr(&device_dir.join("device")).unwrap_or_default();
+            format!("AMD GPU {} ({})", device_id, card_name)
+        });
+
+    let utilization = read_u64(&device_dir.join("gpu_busy_percent")).unwrap_or(0) as f32;
+
+    let total_memory = read_u64(&device_dir.join("mem_info_vram_total")).unwrap_or(0) / 1024 / 1024;
+    let used_memory = read_u64(&device_dir.join("mem_info_vram_used")).unwrap_or(0) / 1024 / 1024;
+    let memory_usage = if total_memory > 0 {
+        (used_memory as f32 / total_memory as f32) * 100.0
+    } else {
+        0.0
+    };
+
+    // Temperature (millidegrees), power (microwatts) and clocks (Hz) live in hwmon
+    let hwmon = find_hwmon(device_dir);
+    let hwmon_value = |file: &str| hwmon.as_ref().and_then(|dir| read_u64(&dir.join(file)));
+
+    let temperature = hwmon_value("temp1_input").map(|millis| (millis / 1000) as u32).unwrap_or(0);
+    let power_draw = hwmon_value("power1_average")
+        .or_else(|| hwmon_value("power1_input"))
+        .map(|microwatts| microwatts as f32 / 1_000_000.0);
+    let graphics_clock = hwmon_value("freq1_input").map(|hz| (hz / 1_000_000) as u32);
+    let memory_clock = hwmon_value("freq2_input").map(|hz| (hz / 1_000_000) as u32);
+
+    Some(GpuInfo {
+        name,
+        utilization,
+        temperature,
+        total_memory,
+        used_memory,
+        memory_usage,
+        vendor: GpuVendor::Amd,
+        power_draw,
+        graphics_clock,
+        memory_clock,
+        ..GpuInfo::default()
+    })
+}
+
+/// Find every AMD GPU under <sysfs_root>/class/drm
+pub fn detect_amd_gpus(sysfs_root: &Path) -> Vec<GpuInfo> {
+    drm_cards(sysfs_root)
+        .iter()
+        .filter_map(|card| {
+            let device_dir = sysfs_root.join("class/drm").join(card).join("device");
+            read_amd_gpu(card, &device_dir)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sysfs::fixture::FixtureDir;
+
+    // Build a throwaway sysfs tree with one AMD card, its connector and an Intel card
+    fn sysfs_fixture(test: &str) -> FixtureDir {
+        let sys = FixtureDir::new(&format!("amd-{}", test));
+        let attrs = [
+            ("vendor", "0x1002"),
+            ("device", "0x744c"),
+            ("product_name", "AMD Radeon RX 7900 XTX"),
+            ("gpu_busy_percent", "37"),
+            ("mem_info_vram_total", "25753026560"),
+            ("mem_info_vram_used", "6438256640"),
+            ("hwmon/hwmon3/temp1_input", "54000"),
+            ("hwmon/hwmon3/power1_average", "212000000"),
+            ("hwmon/hwmon3/freq1_input", "2483000000"),
+            ("hwmon/hwmon3/freq2_input", "1249000000"),
+        ];
+        for (name, value) in attrs {
+            sys.write(&format!("class/drm/card0/device/{}", name), value);
+        }
+        sys.mkdir("class/drm/card0-DP-1")
+            .write("class/drm/card1/device/vendor", "0x8086")
+            .write("class/drm/card1/device/device", "0x4680");
+        sys
+    }
+
+    #[test]
+    fn reads_amdgpu_sysfs_attributes() {
+        let sys = sysfs_fixture("read");
+        let gpu = read_amd_gpu("card0", &sys.path().join("class/drm/card0/device")).unwrap();
+
+        assert_eq!(gpu.name, "AMD Radeon RX 7900 XTX");
+        assert_eq!(gpu.vendor, GpuVendor::Amd);
+        assert_eq!(gpu.utilization, 37.0);
+        assert_eq!(gpu.total_memory, 24560);
+        assert_eq!(gpu.used_memory, 6140);
+        assert!((gpu.memory_usage - 25.0).abs() < 0.01);
+        assert_eq!(gpu.temperature, 54);
+        assert_eq!(gpu.power_draw, Some(212.0));
+        assert_eq!(gpu.graphics_clock, Some(2483));
+        assert_eq!(gpu.memory_clock, Some(1249));
+    }
+
+    #[test]
+    fn only_amd_cards_are_detected() {
+        let sys = sysfs_fixture("detect");
+        let gpus = detect_amd_gpus(sys.path());
+
+        assert_eq!(gpus.len(), 1);
+        assert_eq!(gpus[0].name, "AMD Radeon RX 7900 XTX");
+        assert!(read_amd_gpu("card1", &sys.path().join("class/drm/card1/device")).is_none());
+    }
+}
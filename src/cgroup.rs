@@ -0,0 +1,179 @@
+// cgroup.rs - cgroup v2 detection and resource limits (memory.max, cpu.max,
+// cpuset.cpus.effective) so usage can be shown relative to a container
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::cpu::parse_cpu_list;
+
+/// Find the unified (v2) cgroup path of the current process from /proc/self/cgroup
+pub fn current_cgroup_path(proc_root: &Path) -> Option<String> {
+    let contents = fs::read_to_string(proc_root.join("self/cgroup")).ok()?;
+    contents
+        .lines()
+        .find_map(|line| line.strip_prefix("0::"))
+        .map(|path| path.to_string())
+}
+
+/// Parse a cgroup limit file such as memory.max, where "max" means unlimited
+pub fn parse_limit(contents: &str) -> Option<u64> {
+    match contents.trim() {
+        "max" => None,
+        value => value.parse::<u64>().ok(),
+    }
+}
+
+/// Parse cpu.max ("<quota> <period>" or "max <period>") into a number of CPUs
+pub fn parse_cpu_max(contents: &str) -> Option<f64> {
+    let mut fields = contents.split_whitespace();
+    let quota = fields.next()?.parse::<f64>().ok()?;
+    let period = fields.next()?.parse::<f64>().ok()?;
+    if period > 0.0 {
+        Some(quota / period)
+    } else {
+        None
+    }
+}
+
+/// Look up a key in a flat keyed file such as memory.stat or cpu.stat
+pub fn parse_keyed_value(contents: &str, key: &str) -> Option<u64> {
+    contents.lines().find_map(|line| {
+        let (name, value) = line.split_once(' ')?;
+        if name == key {
+            value.trim().parse::<u64>().ok()
+        } else {
+            None
+        }
+    })
+}
+
+// Limits and usage of the cgroup ezstats runs in
+#[derive(Clone, Debug, Default)]
+pub struct CgroupLimits {
+    pub path: String,
+    pub memory_max: Option<u64>,    // in MB, None if unlimited
+    pub memory_used: u64,           // in MB, excluding inactive page cache
+    pub cpu_quota: Option<f64>,     // in CPUs, None if unlimited
+    pub cpuset: Vec<usize>,         // effective CPUs the cgroup may run on
+    pub cpu_usage: f32,             // percentage of the effective CPU limit
+}
+
+impl CgroupLimits {
+    /// Number of CPUs the cgroup can use, given the host's CPU count
+    pub fn effective_cpus(&self, host_cpus: usize) -> f64 {
+        let mut cpus = host_cpus as f64;
+        if !self.cpuset.is_empty() {
+            cpus = cpus.min(self.cpuset.len() as f64);
+        }
+        if let Some(quota) = self.cpu_quota {
+            cpus = cpus.min(quota);
+        }
+        cpus.max(0.01)
+    }
+
+    /// Whether the cgroup sets a memory limit, a CPU quota or a cpuset
+    /// covering fewer than `host_cpus` CPUs
+    pub fn is_limited(&self, host_cpus: usize) -> bool {
+        self.memory_max.is_some()
+            || self.cpu_quota.is_some()
+            || (!self.cpuset.is_empty() && self.cpuset.len() < host_cpus)
+    }
+
+    /// Memory usage as a percentage of memory.max, or of `host_total` if unlimited
+    pub fn memory_percent(&self, host_total: u64) -> f32 {
+        let limit = self.memory_max.unwrap_or(host_total);
+        if limit > 0 {
+            (self.memory_used as f32 / limit as f32 * 100.0).min(100.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+// cgroup limit monitoring interface
+pub struct CgroupMonitor {
+    proc_root: PathBuf,
+    cgroup_root: PathBuf,
+    limits: Option<CgroupLimits>,
+    previous_usage: Option<(u64, Instant)>,  // cpu.stat usage_usec and when it was read
+}
+
+impl CgroupMonitor {
+    /// Create a cgroup monitor reading from the live /proc and cgroup filesystems
+    pub fn new() -> Self {
+        Self::with_roots("/proc", "/sys/fs/cgroup")
+    }
+
+    /// Create a cgroup monitor reading from alternative proc and cgroup roots
+    pub fn with_roots<P: AsRef<Path>, C: AsRef<Path>>(proc_root: P, cgroup_root: C) -> Self {
+        CgroupMonitor {
+            proc_root: proc_root.as_ref().to_path_buf(),
+            cgroup_root: cgroup_root.as_ref().to_path_buf(),
+            limits: None,
+            previous_usage: None,
+        }
+    }
+
+    /// Re-read the current cgroup's limits and usage
+    pub fn refresh(&mut self, host_cpus: usize) {
+        let path = match current_cgroup_path(&self.proc_root) {
+            Some(path) => path,
+            None => {
+                self.limits = None;
+                return;
+            },
+        };
+
+        let dir = self.cgroup_root.join(path.trim_start_matches('/'));
+        let read = |file: &str| fs::read_to_string(dir.join(file)).ok();
+
+        let to_mb = |bytes: u64| bytes / 1024 / 1024;
+
+        let memory_current = read("memory.current")
+            .and_then(|contents| contents.trim().parse::<u64>().ok())
+            .unwrap_or(0);
+        let inactive_file = read("memory.stat")
+            .and_then(|contents| parse_keyed_value(&contents, "inactive_file"))
+            .unwrap_or(0);
+
+        let mut limits = CgroupLimits {
+            path,
+            memory_max: read("memory.max").as_deref().and_then(parse_limit).map(to_mb),
+            memory_used: to_mb(memory_current.saturating_sub(inactive_file)),
+            cpu_quota: read("cpu.max").as_deref().and_then(parse_cpu_max),
+            cpuset: read("cpuset.cpus.effective")
+                .map(|contents| parse_cpu_list(&contents))
+                .unwrap_or_default(),
+            cpu_usage: 0.0,
+        };
+
+        // CPU usage relative to the limit from the growth of usage_usec
+        let now = Instant::now();
+        if let Some(usage_usec) = read("cpu.stat").and_then(|contents| parse_keyed_value(&contents, "usage_usec")) {
+            if let Some((prev_usec, prev_time)) = self.previous_usage {
+                let elapsed_usec = now.duration_since(prev_time).as_micros() as f64;
+                let capacity = elapsed_usec * limits.effective_cpus(host_cpus);
+                if capacity > 0.0 {
+                    let used = usage_usec.saturating_sub(prev_usec) as f64;
+                    limits.cpu_usage = (used / capacity * 100.0).clamp(0.0, 100.0) as f32;
+                }
+            }
+            self.previous_usage = Some((usage_usec, now));
+        }
+
+        // Most cgroups (including the host's) set none of these; only report
+        // a cgroup that actually constrains ezstats
+        self.limits = if limits.is_limited(host_cpus) {
+            Some(limits)
+        } else {
+            None
+        };
+    }
+
+    /// Get the current cgroup's limits, or None unless it sets a memory limit,
+    /// a CPU quota or a cpuset smaller than the host
+    pub fn get_limits(&self) -> Option<&CgroupLimits> {
+        self.limits.as_ref()
+    }
+}
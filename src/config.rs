@@ -0,0 +1,181 @@
+// config.rs - Optional config file, read once at startup, for the thresholds
+// at which each kind of bar turns yellow and red
+
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+use crate::widget::Thresholds;
+
+/// Thresholds for each kind of metric
+#[derive(Clone, Debug, PartialEq)]
+pub struct ThresholdSet {
+    pub usage: Thresholds,          // CPU, GPU and disk utilization
+    pub memory: Thresholds,
+    pub swap: Thresholds,
+    pub gpu_memory: Thresholds,
+    pub temperature: Thresholds,    // percent of the sensor's critical temperature
+    pub pressure: Thresholds,       // share of time stalled
+    pub battery_charge: Thresholds, // inverted: low charge is bad
+    pub battery_wear: Thresholds,
+}
+
+impl Default for ThresholdSet {
+    fn default() -> Self {
+        ThresholdSet {
+            usage: Thresholds::USAGE,
+            memory: Thresholds::new(70.0, 90.0),
+            swap: Thresholds::new(25.0, 60.0),
+            // ML frameworks reserve nearly all GPU memory up front, so only a truly full GPU is a concern
+            gpu_memory: Thresholds::new(97.0, 99.5),
+            temperature: Thresholds::new(75.0, 90.0),
+            // A few percent of stall time is already felt as latency
+            pressure: Thresholds::new(5.0, 25.0),
+            battery_charge: Thresholds::inverted(50.0, 20.0),
+            battery_wear: Thresholds::new(20.0, 40.0),
+        }
+    }
+}
+
+impl ThresholdSet {
+    // Look up a metric's thresholds by its config key
+    fn get_mut(&mut self, key: &str) -> Option<&mut Thresholds> {
+        match key {
+            "usage" => Some(&mut self.usage),
+            "memory" => Some(&mut self.memory),
+            "swap" => Some(&mut self.swap),
+            "gpu_memory" => Some(&mut self.gpu_memory),
+            "temperature" => Some(&mut self.temperature),
+            "pressure" => Some(&mut self.pressure),
+            "battery_charge" => Some(&mut self.battery_charge),
+            "battery_wear" => Some(&mut self.battery_wear),
+            _ => None,
+        }
+    }
+}
+
+// Parse a `[warning, critical]` pair
+fn parse_pair(value: &str) -> Option<(f32, f32)> {
+    let inner = value.strip_prefix('[')?.strip_suffix(']')?;
+    let (warning, critical) = inner.split_once(',')?;
+    Some((warning.trim().parse().ok()?, critical.trim().parse().ok()?))
+}
+
+/// Parse a config file, applying the `[thresholds]` table over the defaults
+///
+/// Each entry is `<metric> = [warning, critical]`, e.g. `gpu_memory = [95, 99]`.
+/// Whether a scale is inverted is fixed per metric and kept from the defaults.
+/// Other tables are ignored so the file can grow without breaking older builds.
+pub fn parse_config(contents: &str) -> Result<ThresholdSet, String> {
+    let mut set = ThresholdSet::default();
+    let mut in_thresholds = false;
+
+    for (index, line) in contents.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            in_thresholds = line == "[thresholds]";
+            continue;
+        }
+        if !in_thresholds {
+            continue;
+        }
+
+        let error = |message: String| format!("line {}: {}", index + 1, message);
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| error(format!("expected `<metric> = [warning, critical]`, found `{}`", line)))?;
+        let key = key.trim();
+        let thresholds = set
+            .get_mut(key)
+            .ok_or_else(|| error(format!("unknown metric `{}`", key)))?;
+        let (warning, critical) = parse_pair(value.trim())
+            .ok_or_else(|| error(format!("`{}` should be a pair of numbers like [70, 90]", key)))?;
+
+        // The critical threshold must lie beyond the warning one
+        let ordered = if thresholds.inverted { critical <= warning } else { warning <= critical };
+        if !ordered {
+            let direction = if thresholds.inverted { "below" } else { "above" };
+            return Err(error(format!("critical threshold of `{}` must be {} its warning threshold", key, direction)));
+        }
+
+        thresholds.warning = warning;
+        thresholds.critical = critical;
+    }
+
+    Ok(set)
+}
+
+/// Location of the config file: $EZSTATS_CONFIG, or ezstats/config.toml
+/// under $XDG_CONFIG_HOME (~/.config by default)
+pub fn config_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os("EZSTATS_CONFIG") {
+        return Some(PathBuf::from(path));
+    }
+
+    let config_dir = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join("ezstats/config.toml"))
+}
+
+/// Read the config file, falling back to the defaults when there is none
+pub fn load() -> Result<ThresholdSet, String> {
+    let path = match config_path() {
+        Some(path) if path.exists() => path,
+        _ => return Ok(ThresholdSet::default()),
+    };
+
+    let contents = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    parse_config(&contents).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+static THRESHOLDS: OnceLock<ThresholdSet> = OnceLock::new();
+
+/// Install the thresholds loaded at startup; later calls are ignored
+pub fn set_thresholds(thresholds: ThresholdSet) {
+    let _ = THRESHOLDS.set(thresholds);
+}
+
+/// Get the configured thresholds, or the defaults if none were installed
+pub fn thresholds() -> &'static ThresholdSet {
+    THRESHOLDS.get_or_init(ThresholdSet::default)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn thresholds_table_overrides_defaults() {
+        let config = "\
+# ezstats config
+[thresholds]
+gpu_memory = [95, 99]   # allow a fuller GPU
+battery_charge = [40, 10]
+
+[other]
+memory = not a pair
+";
+        let set = parse_config(config).unwrap();
+
+        assert_eq!(set.gpu_memory, Thresholds::new(95.0, 99.0));
+        assert_eq!(set.battery_charge, Thresholds::inverted(40.0, 10.0));
+        assert_eq!(set.memory, ThresholdSet::default().memory);
+    }
+
+    #[test]
+    fn invalid_entries_are_reported_with_their_line() {
+        assert_eq!(
+            parse_config("[thresholds]\ncpu = [1, 2]").unwrap_err(),
+            "line 2: unknown metric `cpu`"
+        );
+        assert!(parse_config("[thresholds]\nmemory = 70").unwrap_err().starts_with("line 2:"));
+        assert!(parse_config("[thresholds]\nmemory = [90, 70]").is_err());
+        assert!(parse_config("[thresholds]\nbattery_charge = [20, 50]").is_err());
+    }
+}
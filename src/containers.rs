@@ -0,0 +1,355 @@
+// containers.rs - Container list derived from process cgroups and cgroup v2
+// stat files, without talking to a container daemon
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::cgroup::parse_keyed_value;
+
+// A container identified from a cgroup path
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContainerId {
+    pub id: String,             // full hexadecimal container ID
+    pub runtime: &'static str,  // docker, podman, containerd, cri-o or unknown
+    pub cgroup_path: String,    // cgroup of the container itself, without nested children
+}
+
+impl ContainerId {
+    /// The 12-character short ID used by docker and podman
+    pub fn short_id(&self) -> &str {
+        &self.id[..self.id.len().min(12)]
+    }
+}
+
+// Resource usage of one container
+#[derive(Clone, Debug)]
+pub struct ContainerInfo {
+    pub container: ContainerId,
+    pub process_count: usize,
+    pub main_process: String,
+    pub cpu_percent: f32,       // 100% equals one fully used CPU, as in docker stats
+    pub memory_mb: u64,         // excludes reclaimable page cache, as in docker stats
+    pub read_bytes_per_sec: f64,
+    pub write_bytes_per_sec: f64,
+}
+
+// Identify the runtime from the text around a container ID
+fn runtime_from_component(component: &str, parent: &str) -> &'static str {
+    if component.starts_with("libpod") || parent.starts_with("libpod") {
+        "podman"
+    } else if component.starts_with("docker") || parent == "docker" {
+        "docker"
+    } else if component.starts_with("cri-containerd") {
+        "containerd"
+    } else if component.starts_with("crio") {
+        "cri-o"
+    } else {
+        "unknown"
+    }
+}
+
+// Find a 64-character hexadecimal run within a cgroup path component
+fn find_container_hex(component: &str) -> Option<&str> {
+    let bytes = component.as_bytes();
+    let mut start = 0;
+
+    while start + 64 <= bytes.len() {
+        let run = bytes[start..].iter().take_while(|b| b.is_ascii_hexdigit()).count();
+        if run >= 64 {
+            return Some(&component[start..start + 64]);
+        }
+        start += run.max(1);
+    }
+
+    None
+}
+
+/// Extract the container from a cgroup path such as
+/// "/system.slice/docker-<id>.scope" or "/machine.slice/libpod-<id>.scope/container"
+pub fn container_from_cgroup_path(path: &str) -> Option<ContainerId> {
+    let components: Vec<&str> = path.split('/').filter(|c| !c.is_empty()).collect();
+
+    for (i, component) in components.iter().enumerate() {
+        if let Some(id) = find_container_hex(component) {
+            let parent = if i > 0 { components[i - 1] } else { "" };
+            return Some(ContainerId {
+                id: id.to_lowercase(),
+                runtime: runtime_from_component(component, parent),
+                cgroup_path: format!("/{}", components[..=i].join("/")),
+            });
+        }
+    }
+
+    None
+}
+
+/// Sum rbytes and wbytes across all devices in an io.stat file
+pub fn parse_io_stat(contents: &str) -> (u64, u64) {
+    let mut read = 0;
+    let mut write = 0;
+
+    for field in contents.split_whitespace() {
+        if let Some((key, value)) = field.split_once('=') {
+            match key {
+                "rbytes" => read += value.parse::<u64>().unwrap_or(0),
+                "wbytes" => write += value.parse::<u64>().unwrap_or(0),
+                _ => {},
+            }
+        }
+    }
+
+    (read, write)
+}
+
+// Cumulative counters from the previous refresh
+struct ContainerSample {
+    usage_usec: u64,
+    read_bytes: u64,
+    write_bytes: u64,
+    taken: Instant,
+}
+
+// Container monitoring interface
+pub struct ContainerMonitor {
+    proc_root: PathBuf,
+    cgroup_root: PathBuf,
+    previous: HashMap<String, ContainerSample>,
+    containers: Vec<ContainerInfo>,
+}
+
+impl ContainerMonitor {
+    /// Create a container monitor reading from the live /proc and cgroup filesystems
+    pub fn new() -> Self {
+        Self::with_roots("/proc", "/sys/fs/cgroup")
+    }
+
+    /// Create a container monitor reading from alternative proc and cgroup roots
+    pub fn with_roots<P: AsRef<Path>, C: AsRef<Path>>(proc_root: P, cgroup_root: C) -> Self {
+        ContainerMonitor {
+            proc_root: proc_root.as_ref().to_path_buf(),
+            cgroup_root: cgroup_root.as_ref().to_path_buf(),
+            previous: HashMap::new(),
+            containers: Vec::new(),
+        }
+    }
+
+    // Group every process by the container its cgroup belongs to, returning
+    // the container and its sorted process IDs
+    fn group_processes(&self) -> Vec<(ContainerId, Vec<u32>)> {
+        let entries = match fs::read_dir(&self.proc_root) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut groups: Vec<(ContainerId, Vec<u32>)> = Vec::new();
+
+        for entry in entries.filter_map(|entry| entry.ok()) {
+            let pid = match entry.file_name().to_str().and_then(|name| name.parse::<u32>().ok()) {
+                Some(pid) => pid,
+                None => continue,
+            };
+
+            let cgroup = match fs::read_to_string(entry.path().join("cgroup")) {
+                Ok(contents) => contents,
+                Err(_) => continue, // process exited while we were scanning
+            };
+
+            let container = cgroup
+                .lines()
+                .find_map(|line| line.strip_prefix("0::"))
+                .and_then(container_from_cgroup_path);
+
+            if let Some(container) = container {
+                match groups.iter_mut().find(|(c, _)| c.id == container.id) {
+                    Some((_, pids)) => pids.push(pid),
+                    None => groups.push((container, vec![pid])),
+                }
+            }
+        }
+
+        for (_, pids) in groups.iter_mut() {
+            pids.sort_unstable();
+        }
+        groups.sort_by(|a, b| a.0.id.cmp(&b.0.id));
+        groups
+    }
+
+    /// Re-scan processes and recompute per-container usage
+    pub fn refresh(&mut self) {
+        self.refresh_at(Instant::now());
+    }
+
+    // Refresh with an explicit timestamp, so tests can control the rate interval
+    fn refresh_at(&mut self, now: Instant) {
+        let mut containers = Vec::new();
+        let mut samples = HashMap::new();
+
+        for (container, pids) in self.group_processes() {
+            let dir = self.cgroup_root.join(container.cgroup_path.trim_start_matches('/'));
+            let read = |file: &str| fs::read_to_string(dir.join(file)).ok();
+
+            let usage_usec = read("cpu.stat")
+                .and_then(|contents| parse_keyed_value(&contents, "usage_usec"))
+                .unwrap_or(0);
+            let memory_bytes = read("memory.current")
+                .and_then(|contents| contents.trim().parse::<u64>().ok())
+                .unwrap_or(0);
+            let inactive_file = read("memory.stat")
+                .and_then(|contents| parse_keyed_value(&contents, "inactive_file"))
+                .unwrap_or(0);
+            let (read_bytes, write_bytes) = read("io.stat")
+                .map(|contents| parse_io_stat(&contents))
+                .unwrap_or((0, 0));
+
+            let main_process = pids
+                .first()
+                .and_then(|pid| fs::read_to_string(self.proc_root.join(pid.to_string()).join("comm")).ok())
+                .map(|comm| comm.trim().to_string())
+                .unwrap_or_default();
+
+            let mut info = ContainerInfo {
+                container: container.clone(),
+                process_count: pids.len(),
+                main_process,
+                cpu_percent: 0.0,
+                memory_mb: memory_bytes.saturating_sub(inactive_file) / 1024 / 1024,
+                read_bytes_per_sec: 0.0,
+                write_bytes_per_sec: 0.0,
+            };
+
+            if let Some(prev) = self.previous.get(&container.id) {
+                let elapsed = now.duration_since(prev.taken).as_secs_f64();
+                if elapsed > 0.0 {
+                    let cpu_usec = usage_usec.saturating_sub(prev.usage_usec) as f64;
+                    info.cpu_percent = (cpu_usec / (elapsed * 1_000_000.0) * 100.0) as f32;
+                    info.read_bytes_per_sec = read_bytes.saturating_sub(prev.read_bytes) as f64 / elapsed;
+                    info.write_bytes_per_sec = write_bytes.saturating_sub(prev.write_bytes) as f64 / elapsed;
+                }
+            }
+
+            samples.insert(container.id.clone(), ContainerSample {
+                usage_usec,
+                read_bytes,
+                write_bytes,
+                taken: now,
+            });
+            containers.push(info);
+        }
+
+        self.previous = samples;
+        self.containers = containers;
+    }
+
+    /// Get the containers found during the last refresh
+    pub fn get_containers(&self) -> &[ContainerInfo] {
+        &self.containers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sysfs::fixture::FixtureDir;
+    use std::time::Duration;
+
+    const ID: &str = "3f4b2a1c9d8e7f6a5b4c3d2e1f0a9b8c7d6e5f4a3b2c1d0e9f8a7b6c5d4e3f2a";
+
+    #[test]
+    fn docker_containers_are_found_with_either_cgroup_driver() {
+        let systemd = container_from_cgroup_path(&format!("/system.slice/docker-{}.scope", ID)).unwrap();
+        assert_eq!(systemd.id, ID);
+        assert_eq!(systemd.runtime, "docker");
+        assert_eq!(systemd.cgroup_path, format!("/system.slice/docker-{}.scope", ID));
+        assert_eq!(systemd.short_id(), "3f4b2a1c9d8e");
+
+        let cgroupfs = container_from_cgroup_path(&format!("/docker/{}", ID)).unwrap();
+        assert_eq!(cgroupfs.runtime, "docker");
+        assert_eq!(cgroupfs.cgroup_path, format!("/docker/{}", ID));
+    }
+
+    #[test]
+    fn podman_container_path_stops_at_the_container_scope() {
+        let path = format!("/machine.slice/libpod-{}.scope/container", ID);
+        let container = container_from_cgroup_path(&path).unwrap();
+
+        assert_eq!(container.id, ID);
+        assert_eq!(container.runtime, "podman");
+        assert_eq!(container.cgroup_path, format!("/machine.slice/libpod-{}.scope", ID));
+    }
+
+    #[test]
+    fn kubernetes_containerd_container_is_found_below_its_pod() {
+        let path = format!(
+            "/kubepods.slice/kubepods-burstable.slice/kubepods-burstable-pod6d1c7e2a_3b4f_4c5d_9e8f_0a1b2c3d4e5f.slice/cri-containerd-{}.scope",
+            ID
+        );
+        let container = container_from_cgroup_path(&path).unwrap();
+
+        assert_eq!(container.id, ID);
+        assert_eq!(container.runtime, "containerd");
+        assert_eq!(container.cgroup_path, path);
+    }
+
+    #[test]
+    fn host_cgroups_are_not_containers() {
+        assert_eq!(container_from_cgroup_path("/user.slice/user-1000.slice/session-2.scope"), None);
+        assert_eq!(container_from_cgroup_path("/"), None);
+    }
+
+    #[test]
+    fn io_stat_bytes_are_summed_across_devices() {
+        let contents = "\
+8:0 rbytes=1459200 wbytes=314773504 rios=192 wios=353 dbytes=0 dios=0
+253:0 rbytes=40960 wbytes=8192 rios=10 wios=2 dbytes=0 dios=0
+";
+        assert_eq!(parse_io_stat(contents), (1500160, 314781696));
+        assert_eq!(parse_io_stat(""), (0, 0));
+    }
+    #[test]
+    fn refresh_groups_processes_and_reads_container_usage() {
+        let scope = format!("system.slice/docker-{}.scope", ID);
+        let proc = FixtureDir::new("containers-proc");
+        let cgroup = FixtureDir::new("containers-cgroup");
+
+        // Two processes in one container and one on the host
+        for pid in ["412", "398"] {
+            proc.write(&format!("{}/cgroup", pid), &format!("0::/{}", scope));
+        }
+        proc.write("398/comm", "nginx");
+        proc.write("77/cgroup", "0::/user.slice/user-1000.slice/session-2.scope");
+
+        let write_usage = |usage_usec: u64, rbytes: u64, wbytes: u64| {
+            cgroup
+                .write(&format!("{}/cpu.stat", scope), &format!("usage_usec {}\nuser_usec 0", usage_usec))
+                .write(&format!("{}/io.stat", scope), &format!("8:0 rbytes={} wbytes={} rios=1 wios=1", rbytes, wbytes));
+        };
+        cgroup
+            .write(&format!("{}/memory.current", scope), &(300 * 1024 * 1024).to_string())
+            .write(&format!("{}/memory.stat", scope), &format!("anon 1\ninactive_file {}", 100 * 1024 * 1024));
+        write_usage(5_000_000, 1_000_000, 0);
+
+        let mut monitor = ContainerMonitor::with_roots(proc.path(), cgroup.path());
+        let start = Instant::now();
+        monitor.refresh_at(start);
+
+        let containers = monitor.get_containers();
+        assert_eq!(containers.len(), 1);
+        let info = &containers[0];
+        assert_eq!(info.container.id, ID);
+        assert_eq!(info.process_count, 2);
+        assert_eq!(info.main_process, "nginx");
+        assert_eq!(info.memory_mb, 200);
+        assert_eq!(info.cpu_percent, 0.0); // no rate until a second sample
+
+        // Half a CPU and 1 MB/s of reads and writes over two seconds
+        write_usage(6_000_000, 3_000_000, 2_000_000);
+        monitor.refresh_at(start + Duration::from_secs(2));
+
+        let info = &monitor.get_containers()[0];
+        assert!((info.cpu_percent - 50.0).abs() < 0.01);
+        assert!((info.read_bytes_per_sec - 1_000_000.0).abs() < 0.01);
+        assert!((info.write_bytes_per_sec - 1_000_000.0).abs() < 0.01);
+    }
+}
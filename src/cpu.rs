@@ -0,0 +1,290 @@
+// cpu.rs - CPU model, frequency and topology details from sysinfo and
+// /sys/devices/system/cpu, plus time breakdowns from /proc/stat
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use sysinfo::{CpuExt, System, SystemExt};
+
+use crate::sysfs::read_u64;
+
+// Per logical CPU (hardware thread) details
+#[derive(Clone, Debug, Default)]
+pub struct LogicalCpu {
+    pub id: usize,
+    pub package_id: usize,          // physical socket
+    pub core_id: usize,             // physical core within the socket
+    pub current_freq_mhz: u64,
+    pub min_freq_mhz: Option<u64>,
+    pub max_freq_mhz: Option<u64>,
+    pub governor: Option<String>,
+}
+
+// Static and slowly changing CPU information
+#[derive(Clone, Debug, Default)]
+pub struct CpuDetails {
+    pub brand: String,
+    pub vendor: String,
+    pub physical_cores: Option<usize>,
+    pub logical_cpus: Vec<LogicalCpu>,
+}
+
+impl CpuDetails {
+    /// Number of distinct sockets
+    pub fn socket_count(&self) -> usize {
+        let mut packages: Vec<usize> = self.logical_cpus.iter().map(|cpu| cpu.package_id).collect();
+        packages.sort_unstable();
+        packages.dedup();
+        packages.len().max(1)
+    }
+
+    /// Lowest and highest frequency supported by any CPU, in MHz
+    pub fn frequency_range(&self) -> Option<(u64, u64)> {
+        let min = self.logical_cpus.iter().filter_map(|cpu| cpu.min_freq_mhz).min()?;
+        let max = self.logical_cpus.iter().filter_map(|cpu| cpu.max_freq_mhz).max()?;
+        Some((min, max))
+    }
+
+    /// Scaling governor of the first CPU that reports one
+    pub fn governor(&self) -> Option<&str> {
+        self.logical_cpus.iter().find_map(|cpu| cpu.governor.as_deref())
+    }
+
+    /// Logical CPU indices ordered so that sockets and hyperthread siblings
+    /// sharing a physical core are adjacent
+    pub fn topology_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.logical_cpus.len()).collect();
+        order.sort_by_key(|&i| {
+            let cpu = &self.logical_cpus[i];
+            (cpu.package_id, cpu.core_id, cpu.id)
+        });
+        order
+    }
+}
+
+// Cumulative CPU time counters in clock ticks, as found in /proc/stat
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CpuTimes {
+    pub user: u64,
+    pub nice: u64,
+    pub system: u64,
+    pub idle: u64,
+    pub iowait: u64,
+    pub irq: u64,
+    pub softirq: u64,
+    pub steal: u64,
+}
+
+impl CpuTimes {
+    fn total(&self) -> u64 {
+        self.user + self.nice + self.system + self.idle + self.iowait + self.irq + self.softirq + self.steal
+    }
+}
+
+// Share of time spent in each state between two samples, as percentages
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CpuBreakdown {
+    pub user: f32,
+    pub nice: f32,
+    pub system: f32,
+    pub iowait: f32,
+    pub irq: f32,       // hard and soft interrupts combined
+    pub steal: f32,
+}
+
+impl CpuBreakdown {
+    /// Compute the breakdown from two cumulative samples
+    pub fn between(prev: &CpuTimes, curr: &CpuTimes) -> Self {
+        let total = curr.total().saturating_sub(prev.total());
+        if total == 0 {
+            return CpuBreakdown::default();
+        }
+
+        let pct = |c: u64, p: u64| c.saturating_sub(p) as f32 / total as f32 * 100.0;
+
+        CpuBreakdown {
+            user: pct(curr.user, prev.user),
+            nice: pct(curr.nice, prev.nice),
+            system: pct(curr.system, prev.system),
+            iowait: pct(curr.iowait, prev.iowait),
+            irq: pct(curr.irq + curr.softirq, prev.irq + prev.softirq),
+            steal: pct(curr.steal, prev.steal),
+        }
+    }
+
+    /// Percentage of time not spent idle or waiting on I/O
+    pub fn busy(&self) -> f32 {
+        self.user + self.nice + self.system + self.irq + self.steal
+    }
+}
+
+/// Parse /proc/stat into the aggregate "cpu" line and the per-CPU "cpuN" lines
+pub fn parse_proc_stat(contents: &str) -> (Option<CpuTimes>, Vec<CpuTimes>) {
+    let mut total = None;
+    let mut per_cpu = Vec::new();
+
+    for line in contents.lines() {
+        let mut fields = line.split_whitespace();
+        let name = match fields.next() {
+            Some(name) if name.starts_with("cpu") => name,
+            _ => continue,
+        };
+
+        let values: Vec<u64> = fields.map(|field| field.parse::<u64>().unwrap_or(0)).collect();
+        let value = |i: usize| values.get(i).copied().unwrap_or(0);
+
+        let times = CpuTimes {
+            user: value(0),
+            nice: value(1),
+            system: value(2),
+            idle: value(3),
+            iowait: value(4),
+            irq: value(5),
+            softirq: value(6),
+            steal: value(7),
+        };
+
+        if name == "cpu" {
+            total = Some(times);
+        } else {
+            per_cpu.push(times);
+        }
+    }
+
+    (total, per_cpu)
+}
+
+/// Parse a kernel CPU list such as "0-3,8,10-11" into individual indices
+pub fn parse_cpu_list(list: &str) -> Vec<usize> {
+    let mut cpus = Vec::new();
+
+    for part in list.trim().split(',').filter(|part| !part.is_empty()) {
+        match part.split_once('-') {
+            Some((start, end)) => {
+                if let (Ok(start), Ok(end)) = (start.trim().parse::<usize>(), end.trim().parse::<usize>()) {
+                    cpus.extend(start..=end);
+                }
+            },
+            None => {
+                if let Ok(cpu) = part.trim().parse::<usize>() {
+                    cpus.push(cpu);
+                }
+            },
+        }
+    }
+
+    cpus
+}
+
+// CPU details monitoring interface
+pub struct CpuMonitor {
+    proc_root: PathBuf,
+    sys_root: PathBuf,
+    details: CpuDetails,
+    previous_times: Option<(CpuTimes, Vec<CpuTimes>)>,
+    total_breakdown: Option<CpuBreakdown>,
+    core_breakdown: Vec<CpuBreakdown>,
+}
+
+impl CpuMonitor {
+    /// Create a CPU monitor reading from the live /proc and /sys filesystems
+    pub fn new() -> Self {
+        Self::with_roots("/proc", "/sys")
+    }
+
+    /// Create a CPU monitor reading from alternative proc and sysfs roots
+    pub fn with_roots<P: AsRef<Path>, S: AsRef<Path>>(proc_root: P, sys_root: S) -> Self {
+        CpuMonitor {
+            proc_root: proc_root.as_ref().to_path_buf(),
+            sys_root: sys_root.as_ref().to_path_buf(),
+            details: CpuDetails::default(),
+            previous_times: None,
+            total_breakdown: None,
+            core_breakdown: Vec::new(),
+        }
+    }
+
+    /// Refresh model, frequency and topology information
+    pub fn refresh(&mut self, system: &System) {
+        let cpus = system.cpus();
+        let (brand, vendor) = match cpus.first() {
+            Some(cpu) => (cpu.brand().trim().to_string(), cpu.vendor_id().to_string()),
+            None => (String::from("Unknown CPU"), String::new()),
+        };
+
+        let logical_cpus = cpus
+            .iter()
+            .enumerate()
+            .map(|(id, cpu)| self.read_logical_cpu(id, cpu.frequency()))
+            .collect();
+
+        self.details = CpuDetails {
+            brand,
+            vendor,
+            physical_cores: system.physical_core_count(),
+            logical_cpus,
+        };
+
+        self.refresh_times();
+    }
+
+    // Sample /proc/stat and compute breakdowns against the previous sample
+    fn refresh_times(&mut self) {
+        let (total, per_cpu) = match fs::read_to_string(self.proc_root.join("stat")) {
+            Ok(contents) => parse_proc_stat(&contents),
+            Err(_) => return,
+        };
+        let total = match total {
+            Some(total) => total,
+            None => return,
+        };
+
+        if let Some((prev_total, prev_per_cpu)) = &self.previous_times {
+            self.total_breakdown = Some(CpuBreakdown::between(prev_total, &total));
+            self.core_breakdown = prev_per_cpu
+                .iter()
+                .zip(per_cpu.iter())
+                .map(|(prev, curr)| CpuBreakdown::between(prev, curr))
+                .collect();
+        }
+
+        self.previous_times = Some((total, per_cpu));
+    }
+
+    // Read topology and cpufreq for one CPU, using sysinfo's frequency when
+    // cpufreq is not exposed
+    fn read_logical_cpu(&self, id: usize, fallback_freq_mhz: u64) -> LogicalCpu {
+        let cpu_dir = self.sys_root.join(format!("devices/system/cpu/cpu{}", id));
+        let topology = cpu_dir.join("topology");
+        let cpufreq = cpu_dir.join("cpufreq");
+
+        // cpufreq values are in kHz
+        let khz_to_mhz = |file: &str| read_u64(&cpufreq.join(file)).map(|khz| khz / 1000);
+
+        LogicalCpu {
+            id,
+            package_id: read_u64(&topology.join("physical_package_id")).unwrap_or(0) as usize,
+            core_id: read_u64(&topology.join("core_id")).unwrap_or(id as u64) as usize,
+            current_freq_mhz: khz_to_mhz("scaling_cur_freq").unwrap_or(fallback_freq_mhz),
+            min_freq_mhz: khz_to_mhz("cpuinfo_min_freq"),
+            max_freq_mhz: khz_to_mhz("cpuinfo_max_freq"),
+            governor: fs::read_to_string(cpufreq.join("scaling_governor"))
+                .ok()
+                .map(|governor| governor.trim().to_string()),
+        }
+    }
+
+    /// Get the most recent CPU details
+    pub fn get_details(&self) -> &CpuDetails {
+        &self.details
+    }
+
+    /// Get the overall time breakdown, once two samples have been taken
+    pub fn get_total_breakdown(&self) -> Option<&CpuBreakdown> {
+        self.total_breakdown.as_ref()
+    }
+
+    /// Get the per-CPU time breakdown, empty until two samples have been taken
+    pub fn get_core_breakdown(&self) -> &[CpuBreakdown] {
+        &self.core_breakdown
+    }
+}
@@ -0,0 +1,249 @@
+// disk.rs - Per-device block I/O statistics parsed from /proc/diskstats
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+// /proc/diskstats always counts in 512-byte sectors, regardless of the device
+const SECTOR_SIZE: u64 = 512;
+
+// Raw cumulative counters for a single block device
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DiskStats {
+    pub name: String,
+    pub reads_completed: u64,
+    pub sectors_read: u64,
+    pub time_reading_ms: u64,
+    pub writes_completed: u64,
+    pub sectors_written: u64,
+    pub time_writing_ms: u64,
+    pub in_flight: u64,
+    pub io_time_ms: u64,
+}
+
+// Per-device rates derived from two consecutive samples
+#[derive(Clone, Debug)]
+pub struct DiskInfo {
+    pub name: String,
+    pub read_bytes_per_sec: f64,
+    pub write_bytes_per_sec: f64,
+    pub read_iops: f64,
+    pub write_iops: f64,
+    pub avg_wait_ms: f64,   // average time per completed request
+    pub utilization: f32,   // percentage of time the device was busy
+    pub in_flight: u64,
+}
+
+/// Parse the contents of /proc/diskstats into raw counters
+///
+/// Lines that do not have at least the 14 classic fields are skipped.
+pub fn parse_diskstats(contents: &str) -> Vec<DiskStats> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 14 {
+                return None;
+            }
+
+            let num = |i: usize| fields[i].parse::<u64>().unwrap_or(0);
+
+            Some(DiskStats {
+                name: fields[2].to_string(),
+                reads_completed: num(3),
+                sectors_read: num(5),
+                time_reading_ms: num(6),
+                writes_completed: num(7),
+                sectors_written: num(9),
+                time_writing_ms: num(10),
+                in_flight: num(11),
+                io_time_ms: num(12),
+            })
+        })
+        .collect()
+}
+
+// Virtual devices that only add noise to the view
+fn is_virtual_device(name: &str) -> bool {
+    ["loop", "ram", "zram", "fd"].iter().any(|prefix| name.starts_with(prefix))
+}
+
+// Ask sysfs whether a device is a partition: whole disks are listed under
+// <sys>/block, partitions carry a `partition` attribute in <sys>/class/block.
+// Names such as dm-10 or nvme0n10 cannot be told apart from their text alone.
+fn is_partition(sys_root: &Path, name: &str) -> bool {
+    // sysfs spells the '/' in names like cciss/c0d0 as '!'
+    let sysfs_name = name.replace('/', "!");
+    if sys_root.join("block").join(&sysfs_name).exists() {
+        return false;
+    }
+    sys_root.join("class/block").join(&sysfs_name).join("partition").exists()
+}
+
+/// Compute rates for one device from two samples taken `elapsed_secs` apart
+pub fn compute_disk_info(prev: &DiskStats, curr: &DiskStats, elapsed_secs: f64) -> DiskInfo {
+    let reads = curr.reads_completed.saturating_sub(prev.reads_completed);
+    let writes = curr.writes_completed.saturating_sub(prev.writes_completed);
+    let wait_ms = curr.time_reading_ms.saturating_sub(prev.time_reading_ms)
+        + curr.time_writing_ms.saturating_sub(prev.time_writing_ms);
+    let busy_ms = curr.io_time_ms.saturating_sub(prev.io_time_ms);
+
+    let per_sec = |delta: u64| {
+        if elapsed_secs > 0.0 {
+            delta as f64 / elapsed_secs
+        } else {
+            0.0
+        }
+    };
+
+    let avg_wait_ms = if reads + writes > 0 {
+        wait_ms as f64 / (reads + writes) as f64
+    } else {
+        0.0
+    };
+
+    let utilization = if elapsed_secs > 0.0 {
+        ((busy_ms as f64 / (elapsed_secs * 1000.0)) * 100.0).clamp(0.0, 100.0) as f32
+    } else {
+        0.0
+    };
+
+    DiskInfo {
+        name: curr.name.clone(),
+        read_bytes_per_sec: per_sec(curr.sectors_read.saturating_sub(prev.sectors_read) * SECTOR_SIZE),
+        write_bytes_per_sec: per_sec(curr.sectors_written.saturating_sub(prev.sectors_written) * SECTOR_SIZE),
+        read_iops: per_sec(reads),
+        write_iops: per_sec(writes),
+        avg_wait_ms,
+        utilization,
+        in_flight: curr.in_flight,
+    }
+}
+
+// Disk I/O monitoring interface
+pub struct DiskMonitor {
+    proc_root: PathBuf,
+    sys_root: PathBuf,
+    previous: HashMap<String, DiskStats>,
+    last_sample: Option<Instant>,
+    current: Vec<DiskInfo>,
+}
+
+impl DiskMonitor {
+    /// Create a disk monitor reading from the live /proc and /sys filesystems
+    pub fn new() -> Self {
+        Self::with_roots("/proc", "/sys")
+    }
+
+    /// Create a disk monitor reading from alternative proc and sysfs roots
+    pub fn with_roots<P: AsRef<Path>, S: AsRef<Path>>(proc_root: P, sys_root: S) -> Self {
+        let mut monitor = DiskMonitor {
+            proc_root: proc_root.as_ref().to_path_buf(),
+            sys_root: sys_root.as_ref().to_path_buf(),
+            previous: HashMap::new(),
+            last_sample: None,
+            current: Vec::new(),
+        };
+
+        // Take a baseline sample so the first refresh can report rates
+        monitor.refresh();
+        monitor
+    }
+
+    /// Read a new sample and recompute the per-device rates
+    pub fn refresh(&mut self) {
+        let contents = match fs::read_to_string(self.proc_root.join("diskstats")) {
+            Ok(contents) => contents,
+            Err(_) => return,
+        };
+
+        let now = Instant::now();
+        let elapsed_secs = self
+            .last_sample
+            .map(|t| now.duration_since(t).as_secs_f64())
+            .unwrap_or(0.0);
+
+        let mut devices = Vec::new();
+        let mut previous = HashMap::new();
+
+        for disk in parse_diskstats(&contents).iter() {
+            if is_virtual_device(&disk.name) || is_partition(&self.sys_root, &disk.name) {
+                continue;
+            }
+
+            let info = match self.previous.get(&disk.name) {
+                Some(prev) => compute_disk_info(prev, disk, elapsed_secs),
+                None => compute_disk_info(disk, disk, 0.0),
+            };
+
+            devices.push(info);
+            previous.insert(disk.name.clone(), disk.clone());
+        }
+
+        self.current = devices;
+        self.previous = previous;
+        self.last_sample = Some(now);
+    }
+
+    /// Get the most recently computed per-device rates
+    pub fn get_disk_info(&self) -> &[DiskInfo] {
+        &self.current
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sysfs::fixture::FixtureDir;
+
+    // Build a throwaway sysfs tree with the given whole disks and partitions
+    fn sysfs_fixture(test: &str, disks: &[&str], partitions: &[&str]) -> FixtureDir {
+        let sys = FixtureDir::new(&format!("disk-{}", test));
+        for disk in disks {
+            sys.mkdir(&format!("block/{}", disk)).mkdir(&format!("class/block/{}", disk));
+        }
+        for (index, partition) in partitions.iter().enumerate() {
+            sys.write(&format!("class/block/{}/partition", partition), &(index + 1).to_string());
+        }
+        sys
+    }
+
+    #[test]
+    fn parses_diskstats_lines() {
+        let contents = "\
+   8       0 sda 48273 12084 3172356 25318 96411 63329 4528256 147231 2 88420 176440 0 0 0 0 4327 3890
+ 259       0 nvme0n1 215603 41 13062314 38873 381925 207183 26117336 428409 0 203820 485962 0 0 0 0 32176 18679
+ 253      10 dm-10 1204 0 48418 532 77 0 616 12 0 544 544 0 0 0 0 0 0
+   8       1 sda1 48109
+";
+        let stats = parse_diskstats(contents);
+
+        assert_eq!(stats.len(), 3);
+        assert_eq!(stats[0], DiskStats {
+            name: String::from("sda"),
+            reads_completed: 48273,
+            sectors_read: 3172356,
+            time_reading_ms: 25318,
+            writes_completed: 96411,
+            sectors_written: 4528256,
+            time_writing_ms: 147231,
+            in_flight: 2,
+            io_time_ms: 88420,
+        });
+        assert_eq!(stats[1].name, "nvme0n1");
+        assert_eq!(stats[2].name, "dm-10");
+        assert_eq!(stats[2].sectors_written, 616);
+    }
+
+    #[test]
+    fn partitions_are_detected_from_sysfs() {
+        let sys = sysfs_fixture("partitions", &["dm-1", "dm-10", "nvme0n1"], &["nvme0n1p1"]);
+        let root = sys.path();
+
+        assert!(!is_partition(root, "dm-1"));
+        assert!(!is_partition(root, "dm-10"));
+        assert!(!is_partition(root, "nvme0n1"));
+        assert!(is_partition(root, "nvme0n1p1"));
+    }
+}
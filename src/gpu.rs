@@ -1,9 +1,12 @@
-// gpu.rs - Unified GPU monitoring with runtime detection for both NVIDIA and Apple GPUs
+// gpu.rs - Unified GPU monitoring with runtime detection for NVIDIA, AMD, Intel and Apple GPUs
 
-use std::time::{Duration, Instant};
+use std::path::{Path, PathBuf};
+
+use crate::amd_gpu;
+use crate::intel_gpu::IntelGpuReader;
 
 // GPU information structure - consistent regardless of GPU type
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct GpuInfo {
     pub name: String,
     pub utilization: f32,
@@ -15,24 +18,59 @@ pub struct GpuInfo {
     // Apple-specific fields
     pub is_low_power: bool,
     pub is_headless: bool,
+    // Optional fields, None when the backend does not report them
+    pub power_draw: Option<f32>,     // in W
+    pub power_limit: Option<f32>,    // in W
+    pub fan_speed: Option<u32>,      // percentage of maximum
+    pub graphics_clock: Option<u32>, // in MHz
+    pub max_graphics_clock: Option<u32>, // in MHz
+    pub memory_clock: Option<u32>,   // in MHz
+    pub sm_clock: Option<u32>,       // in MHz
+    pub encoder_utilization: Option<u32>, // percentage
+    pub decoder_utilization: Option<u32>, // percentage
+    pub pcie_rx: Option<u32>,        // in KB/s
+    pub pcie_tx: Option<u32>,        // in KB/s
+    pub performance_state: Option<u32>, // P-state, 0 is maximum performance
+    pub throttle_reasons: Vec<String>,
+    pub ecc_errors: Option<(u64, u64)>, // volatile (corrected, uncorrected)
+    pub processes: Vec<GpuProcess>,
+}
+
+// A process running on a GPU
+#[derive(Clone, Debug, Default)]
+pub struct GpuProcess {
+    pub pid: u32,
+    pub name: String,               // filled in from the process table
+    pub user: String,               // filled in from the process table
+    pub is_graphics: bool,          // graphics context rather than compute
+    pub used_memory: Option<u64>,   // in MB, None where the driver does not report it
+    pub sm_utilization: Option<u32>, // percentage
 }
 
 // GPU vendor types
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, Default, PartialEq)]
 pub enum GpuVendor {
     Nvidia,
+    Amd,
+    Intel,
     Apple,
+    #[allow(dead_code)]
     Other,
+    #[default]
     None,
 }
 
 // GPU monitoring interface
 pub struct GpuMonitor {
-    // Cache to prevent excessive polling
-    last_refresh: Instant,
-    cache_duration: Duration,
+    // Readings from the last refresh, so every caller in a tick sees the same sample
     cached_info: Vec<GpuInfo>,
     
+    // Root of the sysfs tree used by the AMD backend
+    sysfs_root: PathBuf,
+    
+    // Intel backend, which keeps counters between refreshes
+    intel: IntelGpuReader,
+    
     // NVIDIA support if available
     #[cfg(feature = "nvidia-gpu")]
     nvml: Option<nvml_wrapper::Nvml>,
@@ -42,9 +80,117 @@ pub struct GpuMonitor {
     apple_devices: Option<Vec<metal::Device>>,
 }
 
+// Fill in the optional NVML metrics; each one is skipped when the device or
+// driver does not support it
+#[cfg(feature = "nvidia-gpu")]
+fn read_nvidia_extended(device: &nvml_wrapper::Device, info: &mut GpuInfo) {
+    use nvml_wrapper::bitmasks::device::ThrottleReasons;
+    use nvml_wrapper::enum_wrappers::device::{Clock, EccCounter, MemoryError, PcieUtilCounter};
+    
+    // Power values are reported in milliwatts
+    info.power_draw = device.power_usage().ok().map(|mw| mw as f32 / 1000.0);
+    info.power_limit = device.enforced_power_limit().ok().map(|mw| mw as f32 / 1000.0);
+    info.fan_speed = device.fan_speed(0).ok();
+    
+    info.graphics_clock = device.clock_info(Clock::Graphics).ok();
+    info.memory_clock = device.clock_info(Clock::Memory).ok();
+    info.sm_clock = device.clock_info(Clock::SM).ok();
+    info.max_graphics_clock = device.max_clock_info(Clock::Graphics).ok();
+    
+    info.encoder_utilization = device.encoder_utilization().ok().map(|u| u.utilization);
+    info.decoder_utilization = device.decoder_utilization().ok().map(|u| u.utilization);
+    
+    info.pcie_rx = device.pcie_throughput(PcieUtilCounter::Receive).ok();
+    info.pcie_tx = device.pcie_throughput(PcieUtilCounter::Send).ok();
+    
+    info.performance_state = device.performance_state().ok().map(|state| state.as_c());
+    
+    if let Ok(reasons) = device.current_throttle_reasons() {
+        let names = [
+            (ThrottleReasons::SW_POWER_CAP, "power cap"),
+            (ThrottleReasons::HW_SLOWDOWN, "hw slowdown"),
+            (ThrottleReasons::HW_THERMAL_SLOWDOWN, "hw thermal"),
+            (ThrottleReasons::SW_THERMAL_SLOWDOWN, "sw thermal"),
+            (ThrottleReasons::HW_POWER_BRAKE_SLOWDOWN, "power brake"),
+            (ThrottleReasons::SYNC_BOOST, "sync boost"),
+            (ThrottleReasons::APPLICATIONS_CLOCKS_SETTING, "app clocks"),
+            (ThrottleReasons::DISPLAY_CLOCK_SETTING, "display clocks"),
+            (ThrottleReasons::GPU_IDLE, "idle"),
+        ];
+        info.throttle_reasons = names
+            .iter()
+            .filter(|(flag, _)| reasons.contains(*flag))
+            .map(|(_, name)| name.to_string())
+            .collect();
+    }
+    
+    info.processes = read_nvidia_processes(device);
+    
+    // ECC counters only exist on devices with ECC enabled
+    let corrected = device.total_ecc_errors(MemoryError::Corrected, EccCounter::Volatile);
+    let uncorrected = device.total_ecc_errors(MemoryError::Uncorrected, EccCounter::Volatile);
+    if let (Ok(corrected), Ok(uncorrected)) = (corrected, uncorrected) {
+        info.ecc_errors = Some((corrected, uncorrected));
+    }
+}
+
+// List compute and graphics processes with their memory and, where
+// supported, recent SM utilization
+#[cfg(feature = "nvidia-gpu")]
+fn read_nvidia_processes(device: &nvml_wrapper::Device) -> Vec<GpuProcess> {
+    use nvml_wrapper::enums::device::UsedGpuMemory;
+    use std::time::{SystemTime, UNIX_EPOCH};
+    
+    let mut processes: Vec<GpuProcess> = Vec::new();
+    
+    let compute = device.running_compute_processes().unwrap_or_default();
+    let graphics = device.running_graphics_processes().unwrap_or_default();
+    let listed = compute.iter().map(|p| (p, false)).chain(graphics.iter().map(|p| (p, true)));
+    
+    for (process, is_graphics) in listed {
+        // A process with both contexts is listed once, as compute
+        if processes.iter().any(|p| p.pid == process.pid) {
+            continue;
+        }
+        
+        let used_memory = match process.used_gpu_memory {
+            UsedGpuMemory::Used(bytes) => Some(bytes / 1024 / 1024),
+            UsedGpuMemory::Unavailable => None,
+        };
+        
+        processes.push(GpuProcess {
+            pid: process.pid,
+            is_graphics,
+            used_memory,
+            ..GpuProcess::default()
+        });
+    }
+    
+    // Only ask for samples from the last few seconds (timestamps are in µs)
+    let since = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.as_micros() as u64)
+        .unwrap_or(0)
+        .saturating_sub(5_000_000);
+    
+    if let Ok(samples) = device.process_utilization_stats(since) {
+        for process in processes.iter_mut() {
+            process.sm_utilization = samples
+                .iter()
+                .filter(|sample| sample.pid == process.pid)
+                .max_by_key(|sample| sample.timestamp)
+                .map(|sample| sample.sm_util);
+        }
+    }
+    
+    // Largest memory users first
+    processes.sort_by_key(|process| std::cmp::Reverse(process.used_memory));
+    processes
+}
+
 #[cfg(feature = "apple-gpu")]
 fn now_in_seconds() -> u64 {
-    use std::time::{SystemTime, UNIX_EPOCH};
+    use std::time::{Duration, SystemTime, UNIX_EPOCH};
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .unwrap_or(Duration::from_secs(0))
@@ -54,13 +200,18 @@ fn now_in_seconds() -> u64 {
 impl GpuMonitor {
     /// Initialize the GPU monitoring system with runtime detection
     pub fn new() -> Self {
+        Self::with_sysfs_root("/sys")
+    }
+    
+    /// Initialize GPU monitoring, reading sysfs-based backends from an alternative root
+    pub fn with_sysfs_root<P: AsRef<Path>>(sysfs_root: P) -> Self {
         println!("Initializing GPU monitoring...");
         
         // Create a monitor with empty cache
         let mut monitor = GpuMonitor {
-            last_refresh: Instant::now() - Duration::from_secs(10), // Force initial refresh
-            cache_duration: Duration::from_millis(500),
             cached_info: Vec::new(),
+            sysfs_root: sysfs_root.as_ref().to_path_buf(),
+            intel: IntelGpuReader::new(sysfs_root.as_ref()),
             
             // Try to initialize NVIDIA monitoring if available
             #[cfg(feature = "nvidia-gpu")]
@@ -114,7 +265,7 @@ impl GpuMonitor {
         
         // Perform initial refresh to populate cache
         println!("Initial GPU info refresh...");
-        monitor.cached_info = monitor.refresh_gpu_info();
+        monitor.refresh();
         println!("Found {} GPU(s)", monitor.cached_info.len());
         
         monitor
@@ -130,19 +281,18 @@ impl GpuMonitor {
         self.cached_info.len()
     }
     
-    /// Get GPU information with caching to prevent excessive polling
+    /// Sample every GPU once; call this once per tick
+    pub fn refresh(&mut self) {
+        self.cached_info = self.refresh_gpu_info();
+    }
+    
+    /// Get GPU information from the last refresh
     pub fn get_gpu_info(&self) -> Vec<GpuInfo> {
-        // Check if cache is still valid
-        if self.last_refresh.elapsed() < self.cache_duration && !self.cached_info.is_empty() {
-            return self.cached_info.clone();
-        }
-        
-        // Need to refresh data
-        self.refresh_gpu_info()
+        self.cached_info.clone()
     }
     
     /// Internal method to actually fetch GPU data
-    fn refresh_gpu_info(&self) -> Vec<GpuInfo> {
+    fn refresh_gpu_info(&mut self) -> Vec<GpuInfo> {
         let mut gpu_info = Vec::new();
         
         // Try to get NVIDIA GPU info if available
@@ -181,12 +331,11 @@ impl GpuMonitor {
                             };
                             
                             // Get temperature with fallback
-                            let temp = match device.temperature(nvml_wrapper::enum_wrappers::device::TemperatureSensor::Gpu) {
-                                Ok(t) => t,
-                                Err(_) => 0,
-                            };
+                            let temp = device
+                                .temperature(nvml_wrapper::enum_wrappers::device::TemperatureSensor::Gpu)
+                                .unwrap_or_default();
                             
-                            gpu_info.push(GpuInfo {
+                            let mut info = GpuInfo {
                                 name,
                                 utilization,
                                 temperature: temp,
@@ -194,9 +343,10 @@ impl GpuMonitor {
                                 used_memory: used_mem,
                                 memory_usage: mem_pct,
                                 vendor: GpuVendor::Nvidia,
-                                is_low_power: false,
-                                is_headless: false,
-                            });
+                                ..GpuInfo::default()
+                            };
+                            read_nvidia_extended(&device, &mut info);
+                            gpu_info.push(info);
                         },
                         Err(e) => {
                             eprintln!("Error accessing NVIDIA GPU {}: {:?}", i, e);
@@ -206,6 +356,12 @@ impl GpuMonitor {
             }
         }
         
+        // AMD GPUs are read directly from the amdgpu sysfs interface
+        gpu_info.extend(amd_gpu::detect_amd_gpus(&self.sysfs_root));
+        
+        // Intel integrated and Arc GPUs from the i915/xe sysfs interface
+        gpu_info.extend(self.intel.read_gpus());
+        
         // Try to get Apple GPU info if available
         #[cfg(feature = "apple-gpu")]
         if let Some(devices) = &self.apple_devices {
@@ -232,6 +388,7 @@ impl GpuMonitor {
                     vendor: GpuVendor::Apple,
                     is_low_power,
                     is_headless,
+                    ..GpuInfo::default()
                 });
             }
         }
@@ -0,0 +1,400 @@
+// history.rs - Bounded in-memory time series of sampled metrics, so views can
+// show trends instead of only the latest value
+
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant};
+
+use crate::psi::PsiResource;
+
+// A metric whose history is recorded
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Metric {
+    CpuOverall,
+    CpuCore(usize),
+    MemoryUsed,
+    SwapUsed,
+    CgroupCpu,
+    CgroupMemory,
+    GpuUtilization(usize),
+    GpuMemory(usize),
+    GpuTemperature(usize),
+    GpuPower(usize),
+    Pressure(PsiResource),  // system "some" avg10
+}
+
+impl Metric {
+    /// Human-readable name, e.g. "GPU #0 Utilization"
+    pub fn name(&self) -> String {
+        match self {
+            Metric::CpuOverall => String::from("CPU"),
+            Metric::CpuCore(i) => format!("CPU {}", i),
+            Metric::MemoryUsed => String::from("Memory"),
+            Metric::SwapUsed => String::from("Swap"),
+            Metric::CgroupCpu => String::from("cgroup CPU"),
+            Metric::CgroupMemory => String::from("cgroup Memory"),
+            Metric::GpuUtilization(i) => format!("GPU #{} Utilization", i),
+            Metric::GpuMemory(i) => format!("GPU #{} Memory", i),
+            Metric::GpuTemperature(i) => format!("GPU #{} Temperature", i),
+            Metric::GpuPower(i) => format!("GPU #{} Power", i),
+            Metric::Pressure(resource) => format!("{} pressure", resource.name()),
+        }
+    }
+
+    /// Unit of the recorded values
+    pub fn unit(&self) -> &'static str {
+        match self {
+            Metric::GpuTemperature(_) => "°C",
+            Metric::GpuPower(_) => "W",
+            _ => "%",
+        }
+    }
+
+    // Whether line charts plot the metric over long windows; only these keep
+    // rollups, so e.g. hundreds of per-core series stay raw-only
+    fn has_rollups(&self) -> bool {
+        matches!(
+            self,
+            Metric::CpuOverall
+                | Metric::MemoryUsed
+                | Metric::CgroupCpu
+                | Metric::CgroupMemory
+                | Metric::GpuUtilization(_)
+                | Metric::Pressure(_)
+        )
+    }
+}
+
+// One recorded value
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Sample {
+    pub time: Instant,
+    pub value: f32,
+}
+
+// Summary of the samples recorded in one time bucket
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Rollup {
+    pub start: Instant,
+    pub min: f32,
+    pub max: f32,
+    sum: f32,
+    pub count: u32,
+}
+
+impl Rollup {
+    fn new(start: Instant, value: f32) -> Self {
+        Rollup { start, min: value, max: value, sum: value, count: 1 }
+    }
+
+    fn add(&mut self, value: f32) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.sum += value;
+        self.count += 1;
+    }
+
+    /// Mean of the bucket's samples
+    pub fn average(&self) -> f32 {
+        self.sum / self.count.max(1) as f32
+    }
+}
+
+// Bucket size and retention of each rollup resolution, finest first. Long
+// chart windows read these instead of the raw samples, so a day of history
+// costs a few thousand buckets per metric whatever the sampling interval.
+const ROLLUP_TIERS: [(Duration, Duration); 2] = [
+    (Duration::from_secs(5), Duration::from_secs(60 * 60)),
+    (Duration::from_secs(60), Duration::from_secs(24 * 60 * 60)),
+];
+
+// Most points a query hands to a chart; finer data is read from a rollup
+const MAX_POINTS: usize = 1500;
+
+// Raw samples and rollups of one metric
+#[derive(Default)]
+struct Series {
+    samples: VecDeque<Sample>,
+    rollups: [VecDeque<Rollup>; ROLLUP_TIERS.len()],
+}
+
+// History of every recorded metric: raw samples as a ring buffer bounded by
+// both age and sample count, plus coarser rollups kept for longer
+pub struct HistoryStore {
+    retention: Duration,
+    max_samples: usize,
+    series: HashMap<Metric, Series>,
+}
+
+impl HistoryStore {
+    /// Create a store keeping at most `retention` worth of raw samples, and
+    /// never more than `max_samples` per metric
+    pub fn new(retention: Duration, max_samples: usize) -> Self {
+        HistoryStore {
+            retention,
+            max_samples: max_samples.max(1),
+            series: HashMap::new(),
+        }
+    }
+
+    /// Record a value for a metric at the current time
+    pub fn record(&mut self, metric: Metric, value: f32) {
+        self.record_at(metric, Instant::now(), value);
+    }
+
+    /// Record a value for a metric at a given time
+    ///
+    /// Samples older than the newest recorded one are ignored so each series
+    /// stays ordered by time.
+    pub fn record_at(&mut self, metric: Metric, time: Instant, value: f32) {
+        let series = self.series.entry(metric).or_default();
+        if series.samples.back().map(|last| time < last.time).unwrap_or(false) {
+            return;
+        }
+
+        series.samples.push_back(Sample { time, value });
+
+        while series.samples.len() > self.max_samples {
+            series.samples.pop_front();
+        }
+        while series.samples.front().map(|first| time.duration_since(first.time) > self.retention).unwrap_or(false) {
+            series.samples.pop_front();
+        }
+
+        if !metric.has_rollups() {
+            return;
+        }
+
+        for ((resolution, retention), rollups) in ROLLUP_TIERS.iter().zip(series.rollups.iter_mut()) {
+            match rollups.back_mut() {
+                Some(last) if time.duration_since(last.start) < *resolution => last.add(value),
+                Some(last) => {
+                    // Keep buckets on a fixed grid even across gaps in sampling
+                    let elapsed = time.duration_since(last.start).as_secs_f64();
+                    let skipped = (elapsed / resolution.as_secs_f64()).floor();
+                    let start = last.start + resolution.mul_f64(skipped);
+                    rollups.push_back(Rollup::new(start, value));
+                },
+                None => rollups.push_back(Rollup::new(time, value)),
+            }
+
+            while rollups.front().map(|first| time.duration_since(first.start) > *retention).unwrap_or(false) {
+                rollups.pop_front();
+            }
+        }
+    }
+
+    /// Samples of a metric recorded between `start` and `end` (inclusive), oldest first
+    pub fn range(&self, metric: Metric, start: Instant, end: Instant) -> impl Iterator<Item = &Sample> {
+        let samples = self.series.get(&metric).map(|series| &series.samples);
+        let (first, last) = match samples {
+            Some(samples) => (
+                samples.partition_point(|sample| sample.time < start),
+                samples.partition_point(|sample| sample.time <= end),
+            ),
+            None => (0, 0),
+        };
+
+        samples
+            .into_iter()
+            .flat_map(move |samples| samples.range(first..last.max(first)))
+    }
+
+    /// Values of a metric recorded within the last `window`, oldest first
+    pub fn recent(&self, metric: Metric, window: Duration) -> Vec<f32> {
+        let now = Instant::now();
+        let start = now.checked_sub(window).unwrap_or(now);
+        self.range(metric, start, now).map(|sample| sample.value).collect()
+    }
+
+    /// A metric's history over the last `window` as buckets, oldest first
+    ///
+    /// Raw samples are returned as one-sample buckets while they cover the
+    /// window in at most a chart's worth of points; otherwise the finest
+    /// rollup that does is used. Metrics that are not charted only have
+    /// their raw samples.
+    pub fn rollups(&self, metric: Metric, window: Duration) -> Vec<Rollup> {
+        self.rollups_at(metric, window, Instant::now())
+    }
+
+    /// A metric's history over the `window` ending at `now` as buckets, oldest first
+    pub fn rollups_at(&self, metric: Metric, window: Duration, now: Instant) -> Vec<Rollup> {
+        let series = match self.series.get(&metric) {
+            Some(series) => series,
+            None => return Vec::new(),
+        };
+        let start = now.checked_sub(window).unwrap_or(now);
+
+        let raw: Vec<&Sample> = self.range(metric, start, now).collect();
+        if !metric.has_rollups() || (window <= self.retention && raw.len() <= MAX_POINTS) {
+            return raw.iter().map(|sample| Rollup::new(sample.time, sample.value)).collect();
+        }
+
+        // The coarsest tier serves any window the finer ones cannot
+        let tier = ROLLUP_TIERS
+            .iter()
+            .position(|(resolution, retention)| {
+                window <= *retention && (window.as_secs_f64() / resolution.as_secs_f64()) as usize <= MAX_POINTS
+            })
+            .unwrap_or(ROLLUP_TIERS.len() - 1);
+        let rollups = &series.rollups[tier];
+        let first = rollups.partition_point(|rollup| rollup.start < start);
+        rollups.range(first..).copied().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(store: &HistoryStore, metric: Metric, start: Instant, end: Instant) -> Vec<f32> {
+        store.range(metric, start, end).map(|sample| sample.value).collect()
+    }
+
+    #[test]
+    fn oldest_samples_are_evicted_by_count() {
+        let base = Instant::now();
+        let mut store = HistoryStore::new(Duration::from_secs(3600), 3);
+        for i in 0..5 {
+            store.record_at(Metric::CpuOverall, base + Duration::from_secs(i), i as f32);
+        }
+
+        assert_eq!(values(&store, Metric::CpuOverall, base, base + Duration::from_secs(10)), [2.0, 3.0, 4.0]);
+    }
+
+    #[test]
+    fn samples_older_than_the_retention_are_evicted() {
+        let base = Instant::now();
+        let mut store = HistoryStore::new(Duration::from_secs(10), 100);
+        for secs in [0, 5, 11] {
+            store.record_at(Metric::CpuOverall, base + Duration::from_secs(secs), secs as f32);
+        }
+
+        // 11 s after the first sample it is past the 10 s retention; the one at 5 s is not
+        assert_eq!(values(&store, Metric::CpuOverall, base, base + Duration::from_secs(20)), [5.0, 11.0]);
+    }
+
+    #[test]
+    fn out_of_order_samples_are_dropped() {
+        let base = Instant::now();
+        let mut store = HistoryStore::new(Duration::from_secs(60), 100);
+        store.record_at(Metric::MemoryUsed, base + Duration::from_secs(10), 1.0);
+        store.record_at(Metric::MemoryUsed, base + Duration::from_secs(5), 2.0);
+        store.record_at(Metric::MemoryUsed, base + Duration::from_secs(10), 3.0);
+
+        assert_eq!(values(&store, Metric::MemoryUsed, base, base + Duration::from_secs(20)), [1.0, 3.0]);
+    }
+
+    #[test]
+    fn range_includes_both_boundaries() {
+        let base = Instant::now();
+        let mut store = HistoryStore::new(Duration::from_secs(60), 100);
+        for i in 0..4 {
+            store.record_at(Metric::SwapUsed, base + Duration::from_secs(i), i as f32);
+        }
+
+        let start = base + Duration::from_secs(1);
+        let end = base + Duration::from_secs(2);
+        assert_eq!(values(&store, Metric::SwapUsed, start, end), [1.0, 2.0]);
+        assert!(values(&store, Metric::CpuOverall, start, end).is_empty());
+    }
+
+    #[test]
+    fn recent_returns_values_oldest_first() {
+        let mut store = HistoryStore::new(Duration::from_secs(60), 100);
+        for value in [3.0, 1.0, 2.0] {
+            store.record(Metric::CpuOverall, value);
+        }
+
+        assert_eq!(store.recent(Metric::CpuOverall, Duration::from_secs(60)), [3.0, 1.0, 2.0]);
+    }
+
+    // Record a value every `interval` for `total`, as the app would with a
+    // 15 minute raw retention, returning the time of the last sample
+    fn sampled_store(interval: Duration, total: Duration) -> (HistoryStore, Instant) {
+        let retention = Duration::from_secs(15 * 60);
+        let max_samples = (retention.as_millis() / interval.as_millis()) as usize + 1;
+        let mut store = HistoryStore::new(retention, max_samples);
+
+        let base = Instant::now();
+        let steps = (total.as_millis() / interval.as_millis()) as u32;
+        for step in 0..=steps {
+            let time = base + interval * step;
+            store.record_at(Metric::CpuOverall, time, (step % 100) as f32);
+            store.record_at(Metric::CpuCore(0), time, (step % 100) as f32);
+        }
+        (store, base + interval * steps)
+    }
+
+    // Check that buckets are `resolution` apart and that all but the newest
+    // (which may still be filling) hold `per_bucket` samples
+    fn assert_buckets(rollups: &[Rollup], resolution: Duration, per_bucket: u32) {
+        assert!(rollups.len() > 1);
+        for pair in rollups.windows(2) {
+            assert_eq!(pair[1].start.duration_since(pair[0].start), resolution);
+            assert_eq!(pair[0].count, per_bucket);
+        }
+    }
+
+    #[test]
+    fn chart_windows_pick_raw_samples_or_the_matching_rollup_tier() {
+        let (store, now) = sampled_store(Duration::from_secs(1), Duration::from_secs(25 * 60 * 60));
+        let rollups = |minutes: u64| store.rollups_at(Metric::CpuOverall, Duration::from_secs(minutes * 60), now);
+
+        // Short windows are served from raw samples
+        assert_eq!(rollups(1).len(), 61);
+        assert_eq!(rollups(5).len(), 301);
+        assert!(rollups(5).iter().all(|rollup| rollup.count == 1));
+
+        // An hour uses 5 s buckets and a day 1 minute buckets, including the
+        // bucket starting exactly at the window's start
+        let hour = rollups(60);
+        assert_buckets(&hour, Duration::from_secs(5), 5);
+        assert_eq!(hour.len(), 721);
+        let day = rollups(24 * 60);
+        assert_buckets(&day, Duration::from_secs(60), 60);
+        assert_eq!(day.len(), 1441);
+    }
+
+    #[test]
+    fn fast_sampling_falls_back_to_rollups_once_raw_samples_exceed_a_chart() {
+        let (store, now) = sampled_store(Duration::from_millis(250), Duration::from_secs(20 * 60));
+        let rollups = |minutes: u64| store.rollups_at(Metric::CpuOverall, Duration::from_secs(minutes * 60), now);
+
+        assert_eq!(rollups(1).len(), 241);
+        assert_eq!(rollups(5).len(), 1201);
+
+        // 15 minutes at 4 samples a second is more than a chart's worth of points
+        let quarter = rollups(15);
+        assert_buckets(&quarter, Duration::from_secs(5), 20);
+        assert!(quarter.len() <= MAX_POINTS);
+    }
+
+    #[test]
+    fn rollup_buckets_stay_on_the_grid_across_sampling_gaps() {
+        let base = Instant::now();
+        let mut store = HistoryStore::new(Duration::from_secs(60), 100);
+        for (secs, value) in [(0, 1.0), (1, 2.0), (12, 3.0), (13, 4.0)] {
+            store.record_at(Metric::CpuOverall, base + Duration::from_secs(secs), value);
+        }
+
+        // The hour window is longer than the raw retention, so it reads 5 s buckets
+        let rollups = store.rollups_at(Metric::CpuOverall, Duration::from_secs(60 * 60), base + Duration::from_secs(13));
+        assert_eq!(rollups.len(), 2);
+        assert_eq!(rollups[0].start, base);
+        assert_eq!((rollups[0].min, rollups[0].max, rollups[0].count), (1.0, 2.0, 2));
+        assert_eq!(rollups[1].start, base + Duration::from_secs(10));
+        assert_eq!(rollups[1].average(), 3.5);
+    }
+
+    #[test]
+    fn per_core_metrics_keep_no_rollups() {
+        let (store, now) = sampled_store(Duration::from_secs(1), Duration::from_secs(2 * 60 * 60));
+
+        let series = &store.series[&Metric::CpuCore(0)];
+        assert!(series.rollups.iter().all(|rollups| rollups.is_empty()));
+
+        // Queries fall back to the raw samples still retained
+        let hour = store.rollups_at(Metric::CpuCore(0), Duration::from_secs(60 * 60), now);
+        assert_eq!(hour.len(), 901);
+    }
+}
@@ -0,0 +1,68 @@
+// host.rs - Host identification shown in the frame header: hostname, kernel,
+// uptime, load averages and logged-in users
+
+use std::fs;
+use std::path::Path;
+use sysinfo::{System, SystemExt};
+
+// Location of the login records on Linux
+const UTMP_PATH: &str = "/var/run/utmp";
+
+// Size of a glibc `struct utmp` record and the USER_PROCESS entry type
+const UTMP_RECORD_SIZE: usize = 384;
+const UTMP_USER_PROCESS: i32 = 7;
+
+// Snapshot of host-level information
+#[derive(Clone, Debug, Default)]
+pub struct HostInfo {
+    pub hostname: String,
+    pub kernel_version: String,
+    pub uptime_secs: u64,
+    pub load_average: (f64, f64, f64),  // 1, 5 and 15 minute averages
+    pub logged_in_users: Option<usize>,
+}
+
+impl HostInfo {
+    /// Collect host information from sysinfo and the login records
+    pub fn collect(system: &System) -> Self {
+        let load = system.load_average();
+
+        HostInfo {
+            hostname: system.host_name().unwrap_or_else(|| String::from("unknown")),
+            kernel_version: system.kernel_version().unwrap_or_else(|| String::from("unknown")),
+            uptime_secs: system.uptime(),
+            load_average: (load.one, load.five, load.fifteen),
+            logged_in_users: count_logged_in_users(Path::new(UTMP_PATH)),
+        }
+    }
+
+    /// Format the uptime as e.g. "3d 04:12" or "04:12"
+    pub fn format_uptime(&self) -> String {
+        let days = self.uptime_secs / 86_400;
+        let hours = (self.uptime_secs % 86_400) / 3_600;
+        let minutes = (self.uptime_secs % 3_600) / 60;
+
+        if days > 0 {
+            format!("{}d {:02}:{:02}", days, hours, minutes)
+        } else {
+            format!("{:02}:{:02}", hours, minutes)
+        }
+    }
+}
+
+/// Count USER_PROCESS entries in a utmp file, as `uptime` and `who` do
+///
+/// Returns None if the file cannot be read (e.g. on non-Linux systems).
+pub fn count_logged_in_users(utmp_path: &Path) -> Option<usize> {
+    let data = fs::read(utmp_path).ok()?;
+
+    let count = data
+        .chunks_exact(UTMP_RECORD_SIZE)
+        .filter(|record| {
+            let ut_type = i32::from_ne_bytes([record[0], record[1], record[2], record[3]]);
+            ut_type == UTMP_USER_PROCESS
+        })
+        .count();
+
+    Some(count)
+}
@@ -0,0 +1,153 @@
+// intel_gpu.rs - Intel integrated and Arc GPU monitoring through the i915 and
+// xe drivers' sysfs interfaces
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::gpu::{GpuInfo, GpuVendor};
+use crate::sysfs::{drm_cards, find_hwmon, read_attr, read_u64};
+
+// PCI vendor ID of Intel devices
+const INTEL_PCI_VENDOR: &str = "0x8086";
+
+// Return the value of the first of several candidate files that exists
+fn read_first(candidates: &[PathBuf]) -> Option<u64> {
+    candidates.iter().find_map(|path| read_u64(path))
+}
+
+// Counters from the previous sample of one card
+struct IntelSample {
+    rc6_ms: Option<u64>,
+    energy_uj: Option<u64>,
+    taken: Instant,
+}
+
+// Raw readings from a single Intel card
+struct IntelReadings {
+    name: String,
+    current_freq: Option<u64>,
+    max_freq: Option<u64>,
+    rc6_ms: Option<u64>,
+    energy_uj: Option<u64>,
+    power_uw: Option<u64>,
+    temperature: Option<u64>,
+}
+
+/// Intel GPU reader that remembers RC6 residency and energy counters between
+/// refreshes so busy percentage and power can be derived from their growth
+pub struct IntelGpuReader {
+    sysfs_root: PathBuf,
+    previous: HashMap<String, IntelSample>,
+}
+
+impl IntelGpuReader {
+    /// Create a reader for the DRM cards under <sysfs_root>/class/drm
+    pub fn new<P: AsRef<Path>>(sysfs_root: P) -> Self {
+        IntelGpuReader {
+            sysfs_root: sysfs_root.as_ref().to_path_buf(),
+            previous: HashMap::new(),
+        }
+    }
+
+    // Read frequencies, residency and hwmon values from an Intel card,
+    // covering both the i915 and xe sysfs layouts
+    fn read_card(&self, card: &str) -> Option<IntelReadings> {
+        let card_dir = self.sysfs_root.join("class/drm").join(card);
+        let device_dir = card_dir.join("device");
+
+        if read_attr(&device_dir.join("vendor"))?.to_lowercase() != INTEL_PCI_VENDOR {
+            return None;
+        }
+
+        let xe_gt = device_dir.join("tile0/gt0");
+
+        let current_freq = read_first(&[
+            card_dir.join("gt_act_freq_mhz"),
+            card_dir.join("gt_cur_freq_mhz"),
+            card_dir.join("gt/gt0/rps_act_freq_mhz"),
+            xe_gt.join("freq0/act_freq"),
+            xe_gt.join("freq0/cur_freq"),
+        ]);
+        let max_freq = read_first(&[
+            card_dir.join("gt_max_freq_mhz"),
+            card_dir.join("gt/gt0/rps_max_freq_mhz"),
+            xe_gt.join("freq0/max_freq"),
+        ]);
+        let rc6_ms = read_first(&[
+            card_dir.join("gt/gt0/rc6_residency_ms"),
+            card_dir.join("power/rc6_residency_ms"),
+            xe_gt.join("gtidle/idle_residency_ms"),
+        ]);
+
+        // Discrete Arc cards expose a hwmon directory with energy and temperature
+        let hwmon = find_hwmon(&device_dir);
+        let hwmon_value = |file: &str| hwmon.as_ref().and_then(|dir| read_u64(&dir.join(file)));
+
+        let device_id = read_attr(&device_dir.join("device")).unwrap_or_default();
+
+        Some(IntelReadings {
+            name: format!("Intel GPU {} ({})", device_id, card),
+            current_freq,
+            max_freq,
+            rc6_ms,
+            energy_uj: hwmon_value("energy1_input"),
+            power_uw: hwmon_value("power1_input"),
+            temperature: hwmon_value("temp1_input"),
+        })
+    }
+
+    /// Read every Intel GPU, deriving busy percentage and power from the
+    /// previous call's counters
+    pub fn read_gpus(&mut self) -> Vec<GpuInfo> {
+        let now = Instant::now();
+        let mut gpus = Vec::new();
+
+        for card in drm_cards(&self.sysfs_root) {
+            let readings = match self.read_card(&card) {
+                Some(readings) => readings,
+                None => continue,
+            };
+
+            let mut utilization = 0.0;
+            let mut power_draw = readings.power_uw.map(|uw| uw as f32 / 1_000_000.0);
+
+            if let Some(prev) = self.previous.get(&card) {
+                let elapsed_ms = now.duration_since(prev.taken).as_secs_f64() * 1000.0;
+                if elapsed_ms > 0.0 {
+                    // Time in RC6 is time the GPU was idle
+                    if let (Some(curr), Some(prev)) = (readings.rc6_ms, prev.rc6_ms) {
+                        let idle = curr.saturating_sub(prev) as f64 / elapsed_ms;
+                        utilization = ((1.0 - idle) * 100.0).clamp(0.0, 100.0) as f32;
+                    }
+                    if let (Some(curr), Some(prev)) = (readings.energy_uj, prev.energy_uj) {
+                        let joules = curr.saturating_sub(prev) as f64 / 1_000_000.0;
+                        power_draw = Some((joules / (elapsed_ms / 1000.0)) as f32);
+                    }
+                }
+            }
+
+            self.previous.insert(card.clone(), IntelSample {
+                rc6_ms: readings.rc6_ms,
+                energy_uj: readings.energy_uj,
+                taken: now,
+            });
+
+            gpus.push(GpuInfo {
+                name: readings.name,
+                utilization,
+                temperature: readings.temperature.map(|millis| (millis / 1000) as u32).unwrap_or(0),
+                total_memory: 0, // Integrated GPUs share system memory
+                used_memory: 0,
+                memory_usage: 0.0,
+                vendor: GpuVendor::Intel,
+                power_draw,
+                graphics_clock: readings.current_freq.map(|mhz| mhz as u32),
+                max_graphics_clock: readings.max_freq.map(|mhz| mhz as u32),
+                ..GpuInfo::default()
+            });
+        }
+
+        gpus
+    }
+}
@@ -2,29 +2,65 @@
 // A terminal-based system monitor with interactive UI for displaying
 // real-time CPU, RAM, and GPU usage statistics
 
-use sysinfo::{System, SystemExt, CpuExt};
-use std::{io, thread, time::Duration};
+use sysinfo::{System, SystemExt, CpuExt, Pid, PidExt, ProcessExt, UserExt};
+use std::{io, time::Duration};
 use crossterm::{
     execute,
-    terminal::{self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen},
-    cursor::{MoveTo, Hide, Show},
-    style::{Color, Print, ResetColor, SetForegroundColor},
-    event::{self, Event, KeyCode, KeyModifiers},
+    terminal::{self, EnterAlternateScreen, LeaveAlternateScreen},
+    cursor::{Hide, Show},
 };
 
 mod gpu;
+mod amd_gpu;
+mod intel_gpu;
+mod disk;
+mod sensors;
+mod host;
+mod memory;
+mod cpu;
+mod psi;
+mod cgroup;
+mod containers;
+mod power;
+mod numa;
+mod sysfs;
+mod history;
+mod config;
 mod widget;
 mod ui;
 
-use gpu::{GpuMonitor, GpuInfo, GpuVendor};
-use widget::{Widget, BarChart};
+use gpu::{GpuMonitor, GpuInfo};
+use disk::{DiskMonitor, DiskInfo};
+use sensors::{SensorMonitor, SensorReading};
+use host::HostInfo;
+use memory::{MemoryMonitor, MemInfo};
+use cpu::CpuMonitor;
+use psi::{PsiMonitor, PsiResource};
+use cgroup::{CgroupMonitor, CgroupLimits};
+use containers::{ContainerMonitor, ContainerInfo};
+use power::{PowerMonitor, PowerStatus};
+use numa::NumaMonitor;
+use history::{HistoryStore, Metric};
 use ui::{UiState, ViewType};
 
+// How long sampled metrics are kept for trends and charts
+const HISTORY_RETENTION: Duration = Duration::from_secs(15 * 60);
+
 // Simplified system monitor with unified GPU detection
 struct SystemMonitor {
     system: System,
     refresh_rate: Duration,
     gpu_monitor: GpuMonitor,
+    disk_monitor: DiskMonitor,
+    sensor_monitor: SensorMonitor,
+    memory_monitor: MemoryMonitor,
+    cpu_monitor: CpuMonitor,
+    psi_monitor: PsiMonitor,
+    cgroup_monitor: CgroupMonitor,
+    container_monitor: ContainerMonitor,
+    power_monitor: PowerMonitor,
+    numa_monitor: NumaMonitor,
+    history: HistoryStore,
 }
 
 impl SystemMonitor {
@@ -37,6 +73,45 @@ impl SystemMonitor {
         // Initialize GPU monitoring with automatic detection
         let gpu_monitor = GpuMonitor::new();
         
+        // Initialize block device monitoring
+        let disk_monitor = DiskMonitor::new();
+        
+        // Initialize temperature sensors
+        let mut sensor_monitor = SensorMonitor::new();
+        sensor_monitor.refresh(&system);
+        
+        // Initialize detailed memory breakdown
+        let mut memory_monitor = MemoryMonitor::new();
+        memory_monitor.refresh(&system);
+        
+        // Initialize CPU model, frequency and topology details
+        let mut cpu_monitor = CpuMonitor::new();
+        cpu_monitor.refresh(&system);
+        
+        // Initialize pressure stall monitoring
+        let mut psi_monitor = PsiMonitor::new();
+        psi_monitor.refresh();
+        
+        // Detect cgroup v2 limits when running inside a container
+        let mut cgroup_monitor = CgroupMonitor::new();
+        cgroup_monitor.refresh(system.cpus().len());
+        
+        // Discover containers from process cgroups
+        let mut container_monitor = ContainerMonitor::new();
+        container_monitor.refresh();
+        
+        // Initialize battery and AC adapter monitoring
+        let mut power_monitor = PowerMonitor::new();
+        power_monitor.refresh();
+        
+        // Initialize per-node memory and hugepage monitoring
+        let mut numa_monitor = NumaMonitor::new();
+        numa_monitor.refresh();
+        
+        // Keep one retention period of samples at the refresh rate
+        let max_samples = (HISTORY_RETENTION.as_millis() / refresh_ms.max(1) as u128) as usize + 1;
+        let history = HistoryStore::new(HISTORY_RETENTION, max_samples);
+        
         // Log detected hardware
         println!("Detected {} CPU cores", system.cpus().len());
         println!("Detected {} GPUs", gpu_monitor.device_count());
@@ -45,12 +120,75 @@ impl SystemMonitor {
             system,
             refresh_rate: Duration::from_millis(refresh_ms),
             gpu_monitor,
+            disk_monitor,
+            sensor_monitor,
+            memory_monitor,
+            cpu_monitor,
+            psi_monitor,
+            cgroup_monitor,
+            container_monitor,
+            power_monitor,
+            numa_monitor,
+            history,
         }
     }
     
     /// Refresh all system information
     fn refresh(&mut self) {
         self.system.refresh_all();
+        self.disk_monitor.refresh();
+        self.sensor_monitor.refresh(&self.system);
+        self.memory_monitor.refresh(&self.system);
+        self.cpu_monitor.refresh(&self.system);
+        self.psi_monitor.refresh();
+        self.cgroup_monitor.refresh(self.system.cpus().len());
+        self.container_monitor.refresh();
+        self.power_monitor.refresh();
+        self.numa_monitor.refresh();
+        self.gpu_monitor.refresh();
+        self.record_history();
+    }
+    
+    /// Append the latest host-wide values to the history store
+    fn record_history(&mut self) {
+        let (cpu_per_core, cpu_overall) = self.get_cpu_usage();
+        self.history.record(Metric::CpuOverall, cpu_overall);
+        for (id, usage) in cpu_per_core.iter().enumerate() {
+            self.history.record(Metric::CpuCore(id), *usage);
+        }
+        
+        let mem_info = self.get_memory_info();
+        let (mem_used, swap_used, mem_total) = (mem_info.used_percent(), mem_info.swap_percent(), mem_info.total);
+        self.history.record(Metric::MemoryUsed, mem_used);
+        self.history.record(Metric::SwapUsed, swap_used);
+        
+        // Usage relative to the cgroup's limits, for the cgroup perspective
+        if let Some((cpu_usage, mem_percent)) = self
+            .get_cgroup_limits()
+            .map(|limits| (limits.cpu_usage, limits.memory_percent(mem_total)))
+        {
+            self.history.record(Metric::CgroupCpu, cpu_usage);
+            self.history.record(Metric::CgroupMemory, mem_percent);
+        }
+        
+        for resource in PsiResource::ALL.iter() {
+            if let Some(pressure) = self.psi_monitor.get_system().get(*resource) {
+                self.history.record(Metric::Pressure(*resource), pressure.some.avg10);
+            }
+        }
+        
+        for (id, gpu) in self.gpu_monitor.get_gpu_info().iter().enumerate() {
+            self.history.record(Metric::GpuUtilization(id), gpu.utilization);
+            if gpu.total_memory > 0 {
+                self.history.record(Metric::GpuMemory(id), gpu.memory_usage);
+            }
+            if gpu.temperature > 0 {
+                self.history.record(Metric::GpuTemperature(id), gpu.temperature as f32);
+            }
+            if let Some(watts) = gpu.power_draw {
+                self.history.record(Metric::GpuPower(id), watts);
+            }
+        }
     }
     
     /// Get CPU usage as a percentage for each core and overall
@@ -68,22 +206,77 @@ impl SystemMonitor {
         (per_cpu, overall_usage)
     }
     
-    /// Get memory information in MB
-    fn get_memory_info(&self) -> (u64, u64, f32) {
-        let total_mem = self.system.total_memory() / 1024 / 1024; // Convert to MB
-        let used_mem = self.system.used_memory() / 1024 / 1024;   // Convert to MB
-        let mem_usage_pct = if total_mem > 0 {
-            (used_mem as f32 / total_mem as f32) * 100.0
-        } else {
-            0.0
-        };
-        
-        (total_mem, used_mem, mem_usage_pct)
+    /// Get the detailed memory and swap breakdown in MB
+    fn get_memory_info(&self) -> &MemInfo {
+        self.memory_monitor.get_meminfo()
     }
     
-    /// Get GPU information
+    /// Get GPU information, with process names and users joined in from the process table
     fn get_gpu_info(&self) -> Vec<GpuInfo> {
-        self.gpu_monitor.get_gpu_info()
+        let mut gpu_info = self.gpu_monitor.get_gpu_info();
+        
+        for process in gpu_info.iter_mut().flat_map(|gpu| gpu.processes.iter_mut()) {
+            if let Some(proc_info) = self.system.process(Pid::from_u32(process.pid)) {
+                process.name = proc_info.name().to_string();
+                process.user = proc_info
+                    .user_id()
+                    .and_then(|uid| self.system.get_user_by_id(uid))
+                    .map(|user| user.name().to_string())
+                    .unwrap_or_default();
+            }
+        }
+        
+        gpu_info
+    }
+    
+    /// Get per-device disk I/O rates
+    fn get_disk_info(&self) -> &[DiskInfo] {
+        self.disk_monitor.get_disk_info()
+    }
+    
+    /// Get the latest temperature sensor readings
+    fn get_sensor_readings(&self) -> &[SensorReading] {
+        self.sensor_monitor.get_readings()
+    }
+    
+    /// Get hostname, kernel, uptime, load averages and logged-in users
+    fn get_host_info(&self) -> HostInfo {
+        HostInfo::collect(&self.system)
+    }
+    
+    /// Get the current cgroup's limits if ezstats runs in a limited cgroup
+    fn get_cgroup_limits(&self) -> Option<&CgroupLimits> {
+        self.cgroup_monitor.get_limits()
+    }
+    
+    /// Get containers and their resource usage
+    fn get_containers(&self) -> &[ContainerInfo] {
+        self.container_monitor.get_containers()
+    }
+    
+    /// Get battery and AC adapter status
+    fn get_power_status(&self) -> &PowerStatus {
+        self.power_monitor.get_status()
+    }
+    
+    /// Number of rows that can be selected in the current view's table, or
+    /// metrics to choose from while the distribution is shown
+    fn selectable_rows(&self, ui_state: &UiState) -> usize {
+        let view = ui_state.views.current();
+        if ui_state.show_histogram {
+            let core_count = self.get_cpu_usage().0.len();
+            let metrics = (Metric::CpuOverall, Metric::MemoryUsed);
+            return ui::histogram_metrics(view, core_count, self.get_gpu_info().len(), metrics).len();
+        }
+        
+        match view {
+            ViewType::Containers => self.get_containers().len(),
+            ViewType::GpuDetailed => self.get_gpu_info().len(),
+            ViewType::Compare => {
+                ui::comparison_series(self.get_gpu_info().len(), (Metric::CpuOverall, Metric::MemoryUsed)).len()
+            },
+            _ => 0,
+        }
     }
     
     /// Check if there are any GPUs available
@@ -100,6 +293,7 @@ impl SystemMonitor {
         
         // Create UI state
         let mut ui_state = UiState::new(self.has_gpus());
+        ui_state.has_cgroup = self.get_cgroup_limits().is_some();
         
         // Process events and update display
         let result = self.run_event_loop(&mut stdout, &mut ui_state);
@@ -121,6 +315,9 @@ impl SystemMonitor {
             // Use a shorter polling timeout to improve responsiveness
             if crossterm::event::poll(Duration::from_millis(50))? {
                 if let crossterm::event::Event::Key(key_event) = crossterm::event::read()? {
+                    // Let row selection stop at the end of the current view's table
+                    ui_state.row_count = self.selectable_rows(ui_state);
+                    
                     // Process key event - returns true if UI needs updating
                     let ui_changed = ui::handle_key_event(key_event, ui_state);
                     
@@ -152,15 +349,43 @@ impl SystemMonitor {
     /// Render the current view based on UI state
     fn render_current_view<W: io::Write>(&self, stdout: &mut W, ui_state: &UiState) -> io::Result<()> {
         // Draw common UI frame
-        ui::draw_ui_frame(stdout, ui_state)?;
+        ui::draw_ui_frame(stdout, ui_state, &self.get_host_info(), self.get_power_status())?;
         
         // Get current system metrics
-        let (cpu_per_core, cpu_overall) = self.get_cpu_usage();
-        let (total_mem, used_mem, mem_usage) = self.get_memory_info();
+        let (cpu_per_core, mut cpu_overall) = self.get_cpu_usage();
+        let mem_info = self.get_memory_info();
+        let mut mem_usage = mem_info.used_percent();
+        
+        // In cgroup perspective, usage is relative to the cgroup's limits
+        let cgroup = if ui_state.cgroup_perspective {
+            self.get_cgroup_limits()
+        } else {
+            None
+        };
+        let (mut cpu_metric, mut mem_metric) = (Metric::CpuOverall, Metric::MemoryUsed);
+        if let Some(limits) = cgroup {
+            cpu_overall = limits.cpu_usage;
+            mem_usage = limits.memory_percent(mem_info.total);
+            cpu_metric = Metric::CgroupCpu;
+            mem_metric = Metric::CgroupMemory;
+        }
         
         // Get GPU data
         let gpu_info = self.get_gpu_info();
         
+        // The distribution of one of the view's metrics replaces the view
+        if ui_state.show_histogram {
+            let metrics = ui::histogram_metrics(
+                ui_state.views.current(),
+                cpu_per_core.len(),
+                gpu_info.len(),
+                (cpu_metric, mem_metric),
+            );
+            ui::draw_histogram_view(stdout, &self.history, &metrics, ui_state.selected_row)?;
+            stdout.flush()?;
+            return Ok(());
+        }
+        
         // Draw the appropriate view based on current state
         match ui_state.views.current() {
             ViewType::Overview => {
@@ -169,21 +394,64 @@ impl SystemMonitor {
                     cpu_overall, 
                     mem_usage,
                     &gpu_info,
+                    &self.history,
+                    (cpu_metric, mem_metric),
                 )?;
             },
             ViewType::CpuDetailed => {
-                ui::draw_cpu_view(stdout, cpu_overall, &cpu_per_core)?;
+                ui::draw_cpu_view(
+                    stdout,
+                    cpu_overall,
+                    &cpu_per_core,
+                    &self.cpu_monitor,
+                    cgroup,
+                    &self.history,
+                    ui_state.core_history,
+                )?;
             },
             ViewType::MemoryDetailed => {
-                ui::draw_memory_view(stdout, total_mem, used_mem, mem_usage)?;
+                ui::draw_memory_view(stdout, mem_info, cgroup)?;
             },
             ViewType::GpuDetailed => {
                 if self.has_gpus() {
-                    ui::draw_gpu_view(stdout, &gpu_info)?;
+                    ui::draw_gpu_view(stdout, &gpu_info, ui_state.selected_row)?;
                 } else {
                     ui::draw_no_gpu_view(stdout)?;
                 }
             },
+            ViewType::DiskDetailed => {
+                ui::draw_disk_view(stdout, self.get_disk_info())?;
+            },
+            ViewType::Sensors => {
+                ui::draw_sensors_view(stdout, self.get_sensor_readings())?;
+            },
+            ViewType::Pressure => {
+                ui::draw_psi_view(stdout, &self.psi_monitor, &self.history, ui_state.chart_window())?;
+            },
+            ViewType::Containers => {
+                ui::draw_containers_view(stdout, self.get_containers(), ui_state.selected_row, ui_state.container_sort)?;
+            },
+            ViewType::Power => {
+                ui::draw_power_view(stdout, self.get_power_status())?;
+            },
+            ViewType::Numa => {
+                ui::draw_numa_view(
+                    stdout,
+                    self.numa_monitor.get_nodes(),
+                    self.numa_monitor.get_hugepages(),
+                )?;
+            },
+            ViewType::Compare => {
+                let series = ui::comparison_series(gpu_info.len(), (cpu_metric, mem_metric));
+                ui::draw_compare_view(
+                    stdout,
+                    &self.history,
+                    &series,
+                    ui_state.selected_row,
+                    &ui_state.hidden_series,
+                    ui_state.chart_window(),
+                )?;
+            },
             ViewType::Help => {
                 ui::draw_help_view(stdout)?;
             },
@@ -211,6 +479,12 @@ fn main() -> io::Result<()> {
 
 // The actual application logic
 fn run_app() -> io::Result<()> {
+    // Load color thresholds from the config file, keeping the defaults if it is invalid
+    match config::load() {
+        Ok(thresholds) => config::set_thresholds(thresholds),
+        Err(e) => println!("Ignoring config file: {}", e),
+    }
+    
     // Create system monitor with 1000ms (1 second) refresh rate
     let mut monitor = SystemMonitor::new(1000);
     
@@ -0,0 +1,138 @@
+// memory.rs - Detailed memory and swap breakdown from /proc/meminfo with a
+// sysinfo fallback for platforms without procfs
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use sysinfo::{System, SystemExt};
+
+// Memory breakdown, all values in MB
+#[derive(Clone, Debug, Default)]
+pub struct MemInfo {
+    pub total: u64,
+    pub free: u64,
+    pub available: u64,
+    pub buffers: u64,
+    pub cached: u64,
+    pub shared: u64,
+    pub slab: u64,
+    pub slab_reclaimable: u64,
+    pub dirty: u64,
+    pub swap_total: u64,
+    pub swap_free: u64,
+}
+
+impl MemInfo {
+    /// Memory used by processes, excluding buffers and reclaimable caches
+    ///
+    /// Matches the "used" column of `free`.
+    pub fn used(&self) -> u64 {
+        self.total
+            .saturating_sub(self.free)
+            .saturating_sub(self.buffers)
+            .saturating_sub(self.cached)
+            .saturating_sub(self.slab_reclaimable)
+    }
+
+    /// Used memory as a percentage of total
+    pub fn used_percent(&self) -> f32 {
+        if self.total > 0 {
+            (self.used() as f32 / self.total as f32) * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Swap currently in use
+    pub fn swap_used(&self) -> u64 {
+        self.swap_total.saturating_sub(self.swap_free)
+    }
+
+    /// Used swap as a percentage of total swap
+    pub fn swap_percent(&self) -> f32 {
+        if self.swap_total > 0 {
+            (self.swap_used() as f32 / self.swap_total as f32) * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Build a reduced breakdown from sysinfo where /proc/meminfo is unavailable
+    pub fn from_system(system: &System) -> Self {
+        let to_mb = |bytes: u64| bytes / 1024 / 1024;
+
+        MemInfo {
+            total: to_mb(system.total_memory()),
+            free: to_mb(system.free_memory()),
+            available: to_mb(system.available_memory()),
+            swap_total: to_mb(system.total_swap()),
+            swap_free: to_mb(system.free_swap()),
+            ..MemInfo::default()
+        }
+    }
+}
+
+/// Parse the contents of /proc/meminfo
+///
+/// Returns None if the mandatory MemTotal line is missing.
+pub fn parse_meminfo(contents: &str) -> Option<MemInfo> {
+    let values: HashMap<&str, u64> = contents
+        .lines()
+        .filter_map(|line| {
+            let (key, rest) = line.split_once(':')?;
+            let value = rest.split_whitespace().next()?.parse::<u64>().ok()?;
+            Some((key.trim(), value))
+        })
+        .collect();
+
+    // Values are reported in kB
+    let mb = |key: &str| values.get(key).copied().unwrap_or(0) / 1024;
+
+    Some(MemInfo {
+        total: values.get("MemTotal")? / 1024,
+        free: mb("MemFree"),
+        available: mb("MemAvailable"),
+        buffers: mb("Buffers"),
+        cached: mb("Cached"),
+        shared: mb("Shmem"),
+        slab: mb("Slab"),
+        slab_reclaimable: mb("SReclaimable"),
+        dirty: mb("Dirty"),
+        swap_total: mb("SwapTotal"),
+        swap_free: mb("SwapFree"),
+    })
+}
+
+// Memory breakdown monitoring interface
+pub struct MemoryMonitor {
+    proc_root: PathBuf,
+    info: MemInfo,
+}
+
+impl MemoryMonitor {
+    /// Create a memory monitor reading from the live /proc filesystem
+    pub fn new() -> Self {
+        Self::with_proc_root("/proc")
+    }
+
+    /// Create a memory monitor reading from an alternative proc root
+    pub fn with_proc_root<P: AsRef<Path>>(proc_root: P) -> Self {
+        MemoryMonitor {
+            proc_root: proc_root.as_ref().to_path_buf(),
+            info: MemInfo::default(),
+        }
+    }
+
+    /// Re-read /proc/meminfo, falling back to sysinfo if it is unavailable
+    pub fn refresh(&mut self, system: &System) {
+        self.info = fs::read_to_string(self.proc_root.join("meminfo"))
+            .ok()
+            .and_then(|contents| parse_meminfo(&contents))
+            .unwrap_or_else(|| MemInfo::from_system(system));
+    }
+
+    /// Get the most recent memory breakdown
+    pub fn get_meminfo(&self) -> &MemInfo {
+        &self.info
+    }
+}
@@ -0,0 +1,258 @@
+// numa.rs - Per-NUMA-node memory, CPU lists, allocation counters and
+// hugepage pools from /sys/devices/system/node and /sys/kernel/mm/hugepages
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::cpu::parse_cpu_list;
+use crate::sysfs::read_u64;
+
+// A hugepage pool of one page size
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct HugePagePool {
+    pub size_kb: u64,
+    pub total: u64,             // pages in the pool
+    pub free: u64,
+    pub reserved: Option<u64>,  // promised to mappings but not yet faulted in (system-wide only)
+    pub surplus: u64,           // allocated beyond the pool through overcommit
+}
+
+impl HugePagePool {
+    /// Human-readable page size, e.g. "2 MB" or "1 GB"
+    pub fn size_name(&self) -> String {
+        if self.size_kb >= 1024 * 1024 {
+            format!("{} GB", self.size_kb / 1024 / 1024)
+        } else if self.size_kb >= 1024 {
+            format!("{} MB", self.size_kb / 1024)
+        } else {
+            format!("{} KB", self.size_kb)
+        }
+    }
+
+    /// Memory held by the pool in MB
+    pub fn total_mb(&self) -> u64 {
+        self.total * self.size_kb / 1024
+    }
+}
+
+// Allocation counters from a node's numastat file
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct NumaStat {
+    pub numa_hit: u64,      // allocations intended for and satisfied on this node
+    pub numa_miss: u64,     // allocations intended elsewhere but placed here
+    pub numa_foreign: u64,  // allocations intended for this node but placed elsewhere
+    pub local_node: u64,
+    pub other_node: u64,
+}
+
+// A single NUMA node
+#[derive(Clone, Debug, Default)]
+pub struct NumaNode {
+    pub id: usize,
+    pub cpulist: String,            // as written by the kernel, e.g. "0-15,32-47"
+    pub cpus: Vec<usize>,
+    pub mem_total: u64,             // in MB
+    pub mem_free: u64,              // in MB
+    pub stat: NumaStat,
+    pub miss_per_sec: f64,          // growth of numa_miss + numa_foreign since the last refresh
+    pub hugepages: Vec<HugePagePool>,
+}
+
+impl NumaNode {
+    /// Used memory in MB
+    pub fn mem_used(&self) -> u64 {
+        self.mem_total.saturating_sub(self.mem_free)
+    }
+
+    /// Used memory as a percentage of the node's memory
+    pub fn mem_used_percent(&self) -> f32 {
+        if self.mem_total > 0 {
+            (self.mem_used() as f32 / self.mem_total as f32) * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    /// Share of allocations on this node that landed on the intended node
+    pub fn hit_percent(&self) -> Option<f32> {
+        let total = self.stat.numa_hit + self.stat.numa_miss;
+        if total > 0 {
+            Some(self.stat.numa_hit as f32 / total as f32 * 100.0)
+        } else {
+            None
+        }
+    }
+}
+
+/// Parse a node's meminfo ("Node 0 MemTotal: 32768000 kB") into total and free MB
+pub fn parse_node_meminfo(contents: &str) -> (u64, u64) {
+    let mut total = 0;
+    let mut free = 0;
+
+    for line in contents.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 4 {
+            continue;
+        }
+
+        let value_mb = fields[3].parse::<u64>().unwrap_or(0) / 1024;
+        match fields[2] {
+            "MemTotal:" => total = value_mb,
+            "MemFree:" => free = value_mb,
+            _ => {},
+        }
+    }
+
+    (total, free)
+}
+
+/// Parse a node's numastat file
+pub fn parse_numastat(contents: &str) -> NumaStat {
+    let mut stat = NumaStat::default();
+
+    for line in contents.lines() {
+        if let Some((key, value)) = line.split_once(' ') {
+            let value = value.trim().parse::<u64>().unwrap_or(0);
+            match key {
+                "numa_hit" => stat.numa_hit = value,
+                "numa_miss" => stat.numa_miss = value,
+                "numa_foreign" => stat.numa_foreign = value,
+                "local_node" => stat.local_node = value,
+                "other_node" => stat.other_node = value,
+                _ => {},
+            }
+        }
+    }
+
+    stat
+}
+
+/// Read every hugepages-<size>kB directory below `dir`, smallest page size first
+pub fn read_hugepage_pools(dir: &Path) -> Vec<HugePagePool> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut pools: Vec<HugePagePool> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = entry.file_name().into_string().ok()?;
+            let size_kb = name
+                .strip_prefix("hugepages-")?
+                .strip_suffix("kB")?
+                .parse::<u64>()
+                .ok()?;
+            let pool_dir = entry.path();
+
+            Some(HugePagePool {
+                size_kb,
+                total: read_u64(&pool_dir.join("nr_hugepages")).unwrap_or(0),
+                free: read_u64(&pool_dir.join("free_hugepages")).unwrap_or(0),
+                reserved: read_u64(&pool_dir.join("resv_hugepages")),
+                surplus: read_u64(&pool_dir.join("surplus_hugepages")).unwrap_or(0),
+            })
+        })
+        .collect();
+
+    pools.sort_by_key(|pool| pool.size_kb);
+    pools
+}
+
+// NUMA monitoring interface
+pub struct NumaMonitor {
+    sys_root: PathBuf,
+    nodes: Vec<NumaNode>,
+    hugepages: Vec<HugePagePool>,
+    previous: HashMap<usize, (u64, Instant)>,  // numa_miss + numa_foreign per node
+}
+
+impl NumaMonitor {
+    /// Create a NUMA monitor reading from the live /sys filesystem
+    pub fn new() -> Self {
+        Self::with_sys_root("/sys")
+    }
+
+    /// Create a NUMA monitor reading from an alternative sysfs root
+    pub fn with_sys_root<P: AsRef<Path>>(sys_root: P) -> Self {
+        NumaMonitor {
+            sys_root: sys_root.as_ref().to_path_buf(),
+            nodes: Vec::new(),
+            hugepages: Vec::new(),
+            previous: HashMap::new(),
+        }
+    }
+
+    /// Re-read every node and the system-wide hugepage pools
+    pub fn refresh(&mut self) {
+        self.hugepages = read_hugepage_pools(&self.sys_root.join("kernel/mm/hugepages"));
+
+        let node_root = self.sys_root.join("devices/system/node");
+        let entries = match fs::read_dir(&node_root) {
+            Ok(entries) => entries,
+            Err(_) => {
+                self.nodes.clear();
+                return;
+            },
+        };
+
+        let mut ids: Vec<usize> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter_map(|name| name.strip_prefix("node")?.parse::<usize>().ok())
+            .collect();
+        ids.sort_unstable();
+
+        let now = Instant::now();
+        let mut nodes = Vec::new();
+
+        for id in ids {
+            let dir = node_root.join(format!("node{}", id));
+            let read = |file: &str| fs::read_to_string(dir.join(file)).ok();
+
+            let cpulist = read("cpulist").map(|list| list.trim().to_string()).unwrap_or_default();
+            let (mem_total, mem_free) = read("meminfo")
+                .map(|contents| parse_node_meminfo(&contents))
+                .unwrap_or((0, 0));
+            let stat = read("numastat")
+                .map(|contents| parse_numastat(&contents))
+                .unwrap_or_default();
+
+            // Misses and foreign allocations both mean memory ended up off-node
+            let off_node = stat.numa_miss + stat.numa_foreign;
+            let mut miss_per_sec = 0.0;
+            if let Some((prev_off_node, prev_time)) = self.previous.get(&id) {
+                let elapsed = now.duration_since(*prev_time).as_secs_f64();
+                if elapsed > 0.0 {
+                    miss_per_sec = off_node.saturating_sub(*prev_off_node) as f64 / elapsed;
+                }
+            }
+            self.previous.insert(id, (off_node, now));
+
+            nodes.push(NumaNode {
+                id,
+                cpus: parse_cpu_list(&cpulist),
+                cpulist,
+                mem_total,
+                mem_free,
+                stat,
+                miss_per_sec,
+                hugepages: read_hugepage_pools(&dir.join("hugepages")),
+            });
+        }
+
+        self.nodes = nodes;
+    }
+
+    /// Get the NUMA nodes found during the last refresh
+    pub fn get_nodes(&self) -> &[NumaNode] {
+        &self.nodes
+    }
+
+    /// Get the system-wide hugepage pools
+    pub fn get_hugepages(&self) -> &[HugePagePool] {
+        &self.hugepages
+    }
+}
@@ -0,0 +1,247 @@
+// power.rs - Battery and AC adapter status from /sys/class/power_supply
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::sysfs::{read_attr, read_u64};
+
+// Charging state reported by a battery
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BatteryStatus {
+    Charging,
+    Discharging,
+    NotCharging,
+    Full,
+    Unknown,
+}
+
+impl BatteryStatus {
+    /// Parse the contents of a power supply's status attribute
+    pub fn parse(status: &str) -> Self {
+        match status.trim() {
+            "Charging" => BatteryStatus::Charging,
+            "Discharging" => BatteryStatus::Discharging,
+            "Not charging" => BatteryStatus::NotCharging,
+            "Full" => BatteryStatus::Full,
+            _ => BatteryStatus::Unknown,
+        }
+    }
+
+    // Get a user-friendly name for the status
+    pub fn name(&self) -> &'static str {
+        match self {
+            BatteryStatus::Charging => "Charging",
+            BatteryStatus::Discharging => "Discharging",
+            BatteryStatus::NotCharging => "Not charging",
+            BatteryStatus::Full => "Full",
+            BatteryStatus::Unknown => "Unknown",
+        }
+    }
+}
+
+// A single battery
+#[derive(Clone, Debug)]
+pub struct BatteryInfo {
+    pub name: String,
+    pub model: String,
+    pub technology: String,
+    pub status: BatteryStatus,
+    pub capacity: f32,                      // charge percentage
+    pub power_draw: Option<f32>,            // in W, into or out of the battery
+    pub energy_now: Option<f32>,            // in Wh
+    pub energy_full: Option<f32>,           // in Wh, what the battery holds today
+    pub energy_full_design: Option<f32>,    // in Wh, what it held when new
+    pub cycle_count: Option<u64>,
+}
+
+impl BatteryInfo {
+    /// Full capacity as a percentage of the design capacity
+    pub fn health_percent(&self) -> Option<f32> {
+        match (self.energy_full, self.energy_full_design) {
+            (Some(full), Some(design)) if design > 0.0 => Some(full / design * 100.0),
+            _ => None,
+        }
+    }
+
+    /// Estimated seconds until empty when discharging, or until full when charging
+    pub fn time_remaining_secs(&self) -> Option<u64> {
+        let power = self.power_draw.filter(|watts| *watts > 0.0)?;
+        let energy = self.energy_now?;
+
+        let hours = match self.status {
+            BatteryStatus::Discharging => energy / power,
+            BatteryStatus::Charging => (self.energy_full? - energy).max(0.0) / power,
+            _ => return None,
+        };
+
+        Some((hours * 3600.0) as u64)
+    }
+}
+
+// An AC adapter or USB power source
+#[derive(Clone, Debug)]
+pub struct PowerAdapter {
+    pub name: String,
+    pub online: bool,
+}
+
+/// Format a duration in seconds as "1h 05m"
+pub fn format_duration(secs: u64) -> String {
+    format!("{}h {:02}m", secs / 3600, (secs % 3600) / 60)
+}
+
+/// Read a battery from its /sys/class/power_supply/<name> directory
+///
+/// Batteries report either energy_* (µWh) and power_now (µW), or charge_*
+/// (µAh) and current_now (µA), which are converted with the voltage.
+pub fn read_battery(name: &str, dir: &Path) -> BatteryInfo {
+    // voltage_min_design keeps the Wh figures stable as the voltage sags
+    let volts = read_u64(&dir.join("voltage_min_design"))
+        .or_else(|| read_u64(&dir.join("voltage_now")))
+        .map(|microvolts| microvolts as f32 / 1_000_000.0);
+    let current_volts = read_u64(&dir.join("voltage_now"))
+        .map(|microvolts| microvolts as f32 / 1_000_000.0)
+        .or(volts);
+
+    let energy = |energy_attr: &str, charge_attr: &str| {
+        read_u64(&dir.join(energy_attr))
+            .map(|microwatt_hours| microwatt_hours as f32 / 1_000_000.0)
+            .or_else(|| {
+                let amp_hours = read_u64(&dir.join(charge_attr))? as f32 / 1_000_000.0;
+                Some(amp_hours * volts?)
+            })
+    };
+
+    let energy_now = energy("energy_now", "charge_now");
+    let energy_full = energy("energy_full", "charge_full");
+    let energy_full_design = energy("energy_full_design", "charge_full_design");
+
+    let power_draw = read_u64(&dir.join("power_now"))
+        .map(|microwatts| microwatts as f32 / 1_000_000.0)
+        .or_else(|| {
+            let amps = read_u64(&dir.join("current_now"))? as f32 / 1_000_000.0;
+            Some(amps * current_volts?)
+        });
+
+    // Some firmware omits capacity; derive it from the energy figures
+    let capacity = read_u64(&dir.join("capacity"))
+        .map(|percent| percent as f32)
+        .or_else(|| match (energy_now, energy_full) {
+            (Some(now), Some(full)) if full > 0.0 => Some(now / full * 100.0),
+            _ => None,
+        })
+        .unwrap_or(0.0)
+        .clamp(0.0, 100.0);
+
+    BatteryInfo {
+        name: name.to_string(),
+        model: read_attr(&dir.join("model_name")).unwrap_or_default(),
+        technology: read_attr(&dir.join("technology")).unwrap_or_default(),
+        status: read_attr(&dir.join("status"))
+            .map(|status| BatteryStatus::parse(&status))
+            .unwrap_or(BatteryStatus::Unknown),
+        capacity,
+        power_draw,
+        energy_now,
+        energy_full,
+        energy_full_design,
+        cycle_count: read_u64(&dir.join("cycle_count")).filter(|count| *count > 0),
+    }
+}
+
+// Battery and adapter state from the last refresh
+#[derive(Clone, Debug, Default)]
+pub struct PowerStatus {
+    pub batteries: Vec<BatteryInfo>,
+    pub adapters: Vec<PowerAdapter>,
+}
+
+impl PowerStatus {
+    /// Check whether any adapter is supplying power
+    pub fn on_external_power(&self) -> bool {
+        self.adapters.iter().any(|adapter| adapter.online)
+    }
+
+    /// One-line battery summary for the header, or None without a battery
+    pub fn summary(&self) -> Option<String> {
+        let battery = self.batteries.first()?;
+
+        let mut summary = format!("bat {:.0}% {}", battery.capacity, battery.status.name().to_lowercase());
+        if let Some(secs) = battery.time_remaining_secs() {
+            summary.push_str(&format!(" {}", format_duration(secs)));
+        }
+        if self.on_external_power() {
+            summary.push_str(" (AC)");
+        }
+
+        Some(summary)
+    }
+}
+
+// Power supply monitoring interface
+pub struct PowerMonitor {
+    sys_root: PathBuf,
+    status: PowerStatus,
+}
+
+impl PowerMonitor {
+    /// Create a power monitor reading from the live /sys filesystem
+    pub fn new() -> Self {
+        Self::with_sys_root("/sys")
+    }
+
+    /// Create a power monitor reading from an alternative sysfs root
+    pub fn with_sys_root<P: AsRef<Path>>(sys_root: P) -> Self {
+        PowerMonitor {
+            sys_root: sys_root.as_ref().to_path_buf(),
+            status: PowerStatus::default(),
+        }
+    }
+
+    /// Re-read every power supply
+    pub fn refresh(&mut self) {
+        let supply_dir = self.sys_root.join("class/power_supply");
+        let entries = match fs::read_dir(&supply_dir) {
+            Ok(entries) => entries,
+            Err(_) => {
+                self.status = PowerStatus::default();
+                return;
+            },
+        };
+
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+        names.sort();
+
+        let mut status = PowerStatus::default();
+
+        for name in names {
+            let dir = supply_dir.join(&name);
+
+            // Wireless mice and keyboards report as batteries with a Device scope
+            if read_attr(&dir.join("scope")).as_deref() == Some("Device") {
+                continue;
+            }
+
+            match read_attr(&dir.join("type")).as_deref() {
+                Some("Battery") => status.batteries.push(read_battery(&name, &dir)),
+                Some("Mains") | Some("USB") | Some("USB_C") | Some("USB_PD") => {
+                    status.adapters.push(PowerAdapter {
+                        online: read_u64(&dir.join("online")).unwrap_or(0) == 1,
+                        name,
+                    });
+                },
+                _ => {},
+            }
+        }
+
+        self.status = status;
+    }
+
+    /// Get the batteries and adapters found during the last refresh
+    pub fn get_status(&self) -> &PowerStatus {
+        &self.status
+    }
+}
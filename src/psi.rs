@@ -0,0 +1,176 @@
+// psi.rs - Pressure Stall Information from /proc/pressure and the current
+// cgroup's *.pressure files
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::cgroup::current_cgroup_path;
+
+// Resources that report pressure
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PsiResource {
+    Cpu,
+    Memory,
+    Io,
+}
+
+impl PsiResource {
+    pub const ALL: [PsiResource; 3] = [PsiResource::Cpu, PsiResource::Memory, PsiResource::Io];
+
+    // Name of the pressure file for this resource
+    fn file_name(&self) -> &'static str {
+        match self {
+            PsiResource::Cpu => "cpu",
+            PsiResource::Memory => "memory",
+            PsiResource::Io => "io",
+        }
+    }
+
+    // Get a user-friendly name for the resource
+    pub fn name(&self) -> &'static str {
+        match self {
+            PsiResource::Cpu => "CPU",
+            PsiResource::Memory => "Memory",
+            PsiResource::Io => "I/O",
+        }
+    }
+}
+
+// One line of a pressure file: share of time stalled over three windows
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct PsiAverages {
+    pub avg10: f32,
+    pub avg60: f32,
+    pub avg300: f32,
+    pub total_us: u64,  // cumulative stall time in microseconds
+}
+
+// Pressure for a single resource
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Pressure {
+    pub some: PsiAverages,          // at least one task stalled
+    pub full: Option<PsiAverages>,  // all non-idle tasks stalled (not reported for system CPU on older kernels)
+}
+
+/// Parse the contents of a pressure file
+///
+/// Returns None if the mandatory "some" line is missing.
+pub fn parse_pressure(contents: &str) -> Option<Pressure> {
+    let mut some = None;
+    let mut full = None;
+
+    for line in contents.lines() {
+        let mut fields = line.split_whitespace();
+        let kind = fields.next();
+
+        let mut averages = PsiAverages::default();
+        for field in fields {
+            if let Some((key, value)) = field.split_once('=') {
+                match key {
+                    "avg10" => averages.avg10 = value.parse().unwrap_or(0.0),
+                    "avg60" => averages.avg60 = value.parse().unwrap_or(0.0),
+                    "avg300" => averages.avg300 = value.parse().unwrap_or(0.0),
+                    "total" => averages.total_us = value.parse().unwrap_or(0),
+                    _ => {},
+                }
+            }
+        }
+
+        match kind {
+            Some("some") => some = Some(averages),
+            Some("full") => full = Some(averages),
+            _ => {},
+        }
+    }
+
+    Some(Pressure { some: some?, full })
+}
+
+// Pressure readings for a set of resources
+#[derive(Clone, Debug, Default)]
+pub struct PressureSet {
+    pub cpu: Option<Pressure>,
+    pub memory: Option<Pressure>,
+    pub io: Option<Pressure>,
+}
+
+impl PressureSet {
+    /// Get the reading for a resource
+    pub fn get(&self, resource: PsiResource) -> Option<&Pressure> {
+        match resource {
+            PsiResource::Cpu => self.cpu.as_ref(),
+            PsiResource::Memory => self.memory.as_ref(),
+            PsiResource::Io => self.io.as_ref(),
+        }
+    }
+
+    /// Check if any resource reported pressure
+    pub fn is_empty(&self) -> bool {
+        self.cpu.is_none() && self.memory.is_none() && self.io.is_none()
+    }
+
+    // Read <dir>/<resource><suffix> for every resource
+    fn read(dir: &Path, suffix: &str) -> Self {
+        let read = |resource: PsiResource| {
+            fs::read_to_string(dir.join(format!("{}{}", resource.file_name(), suffix)))
+                .ok()
+                .and_then(|contents| parse_pressure(&contents))
+        };
+
+        PressureSet {
+            cpu: read(PsiResource::Cpu),
+            memory: read(PsiResource::Memory),
+            io: read(PsiResource::Io),
+        }
+    }
+}
+
+// Pressure stall monitoring interface
+pub struct PsiMonitor {
+    proc_root: PathBuf,
+    cgroup_root: PathBuf,
+    system: PressureSet,
+    cgroup: Option<(String, PressureSet)>,
+}
+
+impl PsiMonitor {
+    /// Create a PSI monitor reading from the live /proc and cgroup filesystems
+    pub fn new() -> Self {
+        Self::with_roots("/proc", "/sys/fs/cgroup")
+    }
+
+    /// Create a PSI monitor reading from alternative proc and cgroup roots
+    pub fn with_roots<P: AsRef<Path>, C: AsRef<Path>>(proc_root: P, cgroup_root: C) -> Self {
+        PsiMonitor {
+            proc_root: proc_root.as_ref().to_path_buf(),
+            cgroup_root: cgroup_root.as_ref().to_path_buf(),
+            system: PressureSet::default(),
+            cgroup: None,
+        }
+    }
+
+    /// Re-read system and cgroup pressure
+    pub fn refresh(&mut self) {
+        self.system = PressureSet::read(&self.proc_root.join("pressure"), "");
+
+        // The root cgroup has no pressure files of its own; it is the system view
+        self.cgroup = current_cgroup_path(&self.proc_root)
+            .filter(|path| path != "/")
+            .map(|path| {
+                let dir = self.cgroup_root.join(path.trim_start_matches('/'));
+                let pressure = PressureSet::read(&dir, ".pressure");
+                (path, pressure)
+            })
+            .filter(|(_, pressure)| !pressure.is_empty());
+    }
+
+    /// Get system-wide pressure
+    pub fn get_system(&self) -> &PressureSet {
+        &self.system
+    }
+
+    /// Get the current cgroup's path and pressure, if it is not the root cgroup
+    pub fn get_cgroup(&self) -> Option<&(String, PressureSet)> {
+        self.cgroup.as_ref()
+    }
+}
@@ -0,0 +1,203 @@
+// sensors.rs - Hardware temperature sensors from /sys/class/hwmon with a
+// sysinfo components fallback for platforms without hwmon
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use sysinfo::{ComponentExt, System, SystemExt};
+
+// Temperature used as the scale for sensors that do not report a critical threshold
+pub const DEFAULT_CRITICAL_TEMP: f32 = 100.0;
+
+// Broad category of a sensor, derived from its driver name
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SensorKind {
+    Cpu,
+    Nvme,
+    Chipset,
+    Gpu,
+    Other,
+}
+
+impl SensorKind {
+    /// Classify a sensor by its hwmon driver name or component label
+    pub fn from_chip_name(chip: &str) -> Self {
+        let chip = chip.to_lowercase();
+        if ["coretemp", "k10temp", "k8temp", "zenpower", "cpu", "package"]
+            .iter()
+            .any(|name| chip.contains(name))
+        {
+            SensorKind::Cpu
+        } else if chip.contains("nvme") {
+            SensorKind::Nvme
+        } else if chip.starts_with("pch") || chip.contains("chipset") {
+            SensorKind::Chipset
+        } else if ["amdgpu", "nouveau", "radeon", "gpu"].iter().any(|name| chip.contains(name)) {
+            SensorKind::Gpu
+        } else {
+            SensorKind::Other
+        }
+    }
+
+    // Get a user-friendly name for the sensor category
+    pub fn name(&self) -> &'static str {
+        match self {
+            SensorKind::Cpu => "CPU",
+            SensorKind::Nvme => "NVMe",
+            SensorKind::Chipset => "Chipset",
+            SensorKind::Gpu => "GPU",
+            SensorKind::Other => "Other",
+        }
+    }
+}
+
+// A single temperature reading
+#[derive(Clone, Debug)]
+pub struct SensorReading {
+    pub chip: String,
+    pub label: String,
+    pub kind: SensorKind,
+    pub temperature: f32,       // in °C
+    pub critical: Option<f32>,  // in °C, if the hardware reports one
+}
+
+impl SensorReading {
+    /// Name to show for the sensor, using the chip name for unlabeled inputs
+    pub fn display_name(&self) -> String {
+        if self.label.starts_with("temp") {
+            format!("{} {}", self.chip, self.label.trim_start_matches("temp"))
+        } else {
+            self.label.clone()
+        }
+    }
+
+    /// Temperature as a percentage of the critical threshold (0-100)
+    pub fn percent_of_critical(&self) -> f32 {
+        let critical = self.critical.unwrap_or(DEFAULT_CRITICAL_TEMP);
+        if critical > 0.0 {
+            (self.temperature / critical * 100.0).clamp(0.0, 100.0)
+        } else {
+            0.0
+        }
+    }
+}
+
+// Read a millidegree value from a hwmon attribute file
+fn read_millidegrees(path: &Path) -> Option<f32> {
+    fs::read_to_string(path)
+        .ok()?
+        .trim()
+        .parse::<i64>()
+        .ok()
+        .map(|millis| millis as f32 / 1000.0)
+}
+
+/// Read every temp*_input from a single hwmon directory
+pub fn read_hwmon_chip(dir: &Path) -> Vec<SensorReading> {
+    let chip = fs::read_to_string(dir.join("name"))
+        .map(|name| name.trim().to_string())
+        .unwrap_or_else(|_| String::from("unknown"));
+
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut inputs: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.starts_with("temp") && name.ends_with("_input"))
+        .collect();
+    inputs.sort_by_key(|name| {
+        name.trim_start_matches("temp")
+            .trim_end_matches("_input")
+            .parse::<u32>()
+            .unwrap_or(u32::MAX)
+    });
+
+    inputs
+        .iter()
+        .filter_map(|input| {
+            let prefix = input.trim_end_matches("_input");
+            let temperature = read_millidegrees(&dir.join(input))?;
+
+            let label = fs::read_to_string(dir.join(format!("{}_label", prefix)))
+                .map(|label| label.trim().to_string())
+                .unwrap_or_else(|_| prefix.to_string());
+
+            let critical = read_millidegrees(&dir.join(format!("{}_crit", prefix)))
+                .filter(|crit| *crit > 0.0);
+
+            Some(SensorReading {
+                kind: SensorKind::from_chip_name(&chip),
+                chip: chip.clone(),
+                label,
+                temperature,
+                critical,
+            })
+        })
+        .collect()
+}
+
+// Temperature sensor monitoring interface
+pub struct SensorMonitor {
+    sys_root: PathBuf,
+    readings: Vec<SensorReading>,
+}
+
+impl SensorMonitor {
+    /// Create a sensor monitor reading from the live /sys filesystem
+    pub fn new() -> Self {
+        Self::with_sys_root("/sys")
+    }
+
+    /// Create a sensor monitor reading from an alternative sysfs root
+    pub fn with_sys_root<P: AsRef<Path>>(sys_root: P) -> Self {
+        SensorMonitor {
+            sys_root: sys_root.as_ref().to_path_buf(),
+            readings: Vec::new(),
+        }
+    }
+
+    /// Re-read all sensors, falling back to sysinfo components without hwmon
+    pub fn refresh(&mut self, system: &System) {
+        let mut readings = self.read_hwmon();
+
+        if readings.is_empty() {
+            readings = system
+                .components()
+                .iter()
+                .map(|component| SensorReading {
+                    chip: String::from("component"),
+                    label: component.label().to_string(),
+                    kind: SensorKind::from_chip_name(component.label()),
+                    temperature: component.temperature(),
+                    critical: component.critical(),
+                })
+                .collect();
+        }
+
+        self.readings = readings;
+    }
+
+    // Walk /sys/class/hwmon/hwmon* in a stable order
+    fn read_hwmon(&self) -> Vec<SensorReading> {
+        let hwmon_dir = self.sys_root.join("class/hwmon");
+        let entries = match fs::read_dir(&hwmon_dir) {
+            Ok(entries) => entries,
+            Err(_) => return Vec::new(),
+        };
+
+        let mut chips: Vec<PathBuf> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .collect();
+        chips.sort();
+
+        chips.iter().flat_map(|chip| read_hwmon_chip(chip)).collect()
+    }
+
+    /// Get the most recent sensor readings
+    pub fn get_readings(&self) -> &[SensorReading] {
+        &self.readings
+    }
+}
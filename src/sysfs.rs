@@ -0,0 +1,94 @@
+// sysfs.rs - Shared helpers for reading sysfs attributes and finding DRM cards
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Read a trimmed sysfs attribute
+pub fn read_attr(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok().map(|value| value.trim().to_string())
+}
+
+/// Read a numeric sysfs attribute
+pub fn read_u64(path: &Path) -> Option<u64> {
+    read_attr(path)?.parse::<u64>().ok()
+}
+
+/// Find the first hwmon directory of a device
+pub fn find_hwmon(device_dir: &Path) -> Option<PathBuf> {
+    fs::read_dir(device_dir.join("hwmon"))
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .min()
+}
+
+// Check whether a DRM entry is a card (card0) rather than a connector (card0-DP-1)
+fn is_card_name(name: &str) -> bool {
+    name.strip_prefix("card")
+        .map(|index| !index.is_empty() && index.chars().all(|c| c.is_ascii_digit()))
+        .unwrap_or(false)
+}
+
+/// List the DRM cards under <sysfs_root>/class/drm in index order
+pub fn drm_cards(sysfs_root: &Path) -> Vec<String> {
+    let entries = match fs::read_dir(sysfs_root.join("class/drm")) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut cards: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| is_card_name(name))
+        .collect();
+    cards.sort_by_key(|name| name[4..].parse::<u32>().unwrap_or(u32::MAX));
+    cards
+}
+
+/// Throwaway directory trees standing in for /sys, /proc or a cgroupfs in tests
+#[cfg(test)]
+pub mod fixture {
+    use std::fs;
+    use std::path::{Path, PathBuf};
+
+    /// A temporary directory that is removed when dropped, even if a test fails
+    pub struct FixtureDir {
+        root: PathBuf,
+    }
+
+    impl FixtureDir {
+        /// Create an empty directory unique to `name` and this test process
+        pub fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!("ezstats-{}-{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&root);
+            fs::create_dir_all(&root).unwrap();
+            FixtureDir { root }
+        }
+
+        /// Root of the tree
+        pub fn path(&self) -> &Path {
+            &self.root
+        }
+
+        /// Create a directory (and its parents) below the root
+        pub fn mkdir(&self, dir: &str) -> &Self {
+            fs::create_dir_all(self.root.join(dir)).unwrap();
+            self
+        }
+
+        /// Write a file below the root, creating its directories; a trailing
+        /// newline is added as sysfs and procfs files have one
+        pub fn write(&self, file: &str, contents: &str) -> &Self {
+            let path = self.root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, format!("{}\n", contents)).unwrap();
+            self
+        }
+    }
+
+    impl Drop for FixtureDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.root);
+        }
+    }
+}
@@ -3,6 +3,7 @@
 // Interactive UI system with views and keyboard navigation
 // Simplified to remove feature flags and unify GPU display
 
+use std::collections::HashSet;
 use std::io::{self, Write};
 use std::time::{Duration, Instant};
 use crossterm::{
@@ -14,8 +15,38 @@ use crossterm::{
 };
 
 use crate::widget::Widget;
-use crate::gpu::{GpuInfo, GpuVendor};
-use crate::widget::BarChart;
+use crate::gpu::{GpuInfo, GpuProcess, GpuVendor};
+use crate::disk::DiskInfo;
+use crate::host::HostInfo;
+use crate::memory::MemInfo;
+use crate::cpu::{CpuBreakdown, CpuMonitor};
+use crate::psi::{Pressure, PsiMonitor, PsiResource};
+use crate::cgroup::CgroupLimits;
+use crate::containers::ContainerInfo;
+use crate::power::{format_duration, BatteryStatus, PowerStatus};
+use crate::numa::{HugePagePool, NumaNode};
+use crate::sensors::{SensorKind, SensorReading};
+use crate::widget::{
+    usage_color, Align, BarChart, BarSegment, ChartSeries, Heatmap, HeatmapCell, LineChart, SortOrder,
+    Histogram, Sparkline, StackedBar, Table, TableCell, TableColumn, Thresholds, SEGMENT_COLORS,
+};
+use crate::history::{HistoryStore, Metric};
+use crate::config::thresholds;
+
+// How much history the sparklines next to bars cover
+const SPARKLINE_WINDOW: Duration = Duration::from_secs(120);
+
+// How much history the line charts in detail views can cover, zoomed with +/-
+const CHART_WINDOWS: [Duration; 5] = [
+    Duration::from_secs(60),
+    Duration::from_secs(5 * 60),
+    Duration::from_secs(15 * 60),
+    Duration::from_secs(60 * 60),
+    Duration::from_secs(24 * 60 * 60),
+];
+
+// How much history the distribution histogram covers
+const HISTOGRAM_WINDOW: Duration = Duration::from_secs(15 * 60);
 
 // View types that can be displayed
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -24,6 +55,13 @@ pub enum ViewType {
     CpuDetailed,
     MemoryDetailed,
     GpuDetailed,
+    DiskDetailed,
+    Sensors,
+    Pressure,
+    Containers,
+    Power,
+    Numa,
+    Compare,
     Help,
 }
 
@@ -35,6 +73,13 @@ impl ViewType {
             ViewType::CpuDetailed => "CPU Details",
             ViewType::MemoryDetailed => "Memory Details",
             ViewType::GpuDetailed => "GPU Details",
+            ViewType::DiskDetailed => "Disk I/O",
+            ViewType::Sensors => "Sensors",
+            ViewType::Pressure => "Pressure",
+            ViewType::Containers => "Containers",
+            ViewType::Power => "Power",
+            ViewType::Numa => "NUMA",
+            ViewType::Compare => "Compare",
             ViewType::Help => "Help",
         }
     }
@@ -59,6 +104,13 @@ impl Views {
             available.push(ViewType::GpuDetailed);
         }
         
+        available.push(ViewType::DiskDetailed);
+        available.push(ViewType::Sensors);
+        available.push(ViewType::Pressure);
+        available.push(ViewType::Containers);
+        available.push(ViewType::Power);
+        available.push(ViewType::Numa);
+        available.push(ViewType::Compare);
         available.push(ViewType::Help);
         
         Views {
@@ -103,6 +155,15 @@ pub struct UiState {
     pub automatic_refresh: bool,
     pub last_update: Instant,
     pub show_help_line: bool,
+    pub has_cgroup: bool,
+    pub cgroup_perspective: bool,  // show usage relative to cgroup limits instead of the host
+    pub core_history: bool,        // show recent history in the per-core heatmap
+    pub selected_row: usize,       // highlighted row in the current view's table
+    pub row_count: usize,          // rows the current view's table has to select from
+    pub hidden_series: HashSet<usize>,  // comparison chart series switched off, by position
+    pub show_histogram: bool,      // show the distribution of one of the view's metrics instead
+    pub chart_zoom: usize,         // index into CHART_WINDOWS of the line charts' window
+    pub container_sort: (usize, SortOrder),  // column and direction of the containers table
 }
 
 impl UiState {
@@ -113,6 +174,15 @@ impl UiState {
             automatic_refresh: true,
             last_update: Instant::now(),
             show_help_line: true,
+            has_cgroup: false,
+            cgroup_perspective: false,
+            core_history: false,
+            selected_row: 0,
+            row_count: 0,
+            hidden_series: HashSet::new(),
+            show_histogram: false,
+            chart_zoom: 1,
+            container_sort: (0, SortOrder::Ascending),
         }
     }
     
@@ -120,6 +190,69 @@ impl UiState {
         self.automatic_refresh = !self.automatic_refresh;
     }
     
+    pub fn toggle_cgroup_perspective(&mut self) {
+        if self.has_cgroup {
+            self.cgroup_perspective = !self.cgroup_perspective;
+        }
+    }
+    
+    pub fn toggle_core_history(&mut self) {
+        self.core_history = !self.core_history;
+    }
+    
+    pub fn select_next(&mut self) {
+        if self.selected_row + 1 < self.row_count {
+            self.selected_row += 1;
+        }
+    }
+    
+    pub fn select_previous(&mut self) {
+        self.selected_row = self.selected_row.saturating_sub(1);
+    }
+    
+    pub fn toggle_histogram(&mut self) {
+        if self.views.current() != ViewType::Help {
+            self.show_histogram = !self.show_histogram;
+            self.selected_row = 0;
+        }
+    }
+    
+    /// Time window shown by the line charts
+    pub fn chart_window(&self) -> Duration {
+        CHART_WINDOWS[self.chart_zoom]
+    }
+    
+    pub fn zoom_in(&mut self) {
+        self.chart_zoom = self.chart_zoom.saturating_sub(1);
+    }
+    
+    pub fn zoom_out(&mut self) {
+        self.chart_zoom = (self.chart_zoom + 1).min(CHART_WINDOWS.len() - 1);
+    }
+    
+    pub fn toggle_selected_series(&mut self) {
+        if !self.hidden_series.remove(&self.selected_row) {
+            self.hidden_series.insert(self.selected_row);
+        }
+    }
+    
+    /// Sort the containers table by the next column, keeping the direction
+    pub fn next_sort_column(&mut self) {
+        let (column, order) = self.container_sort;
+        self.container_sort = ((column + 1) % container_columns().len(), order);
+        self.selected_row = 0;
+    }
+    
+    pub fn reverse_sort(&mut self) {
+        let (column, order) = self.container_sort;
+        let order = match order {
+            SortOrder::Ascending => SortOrder::Descending,
+            SortOrder::Descending => SortOrder::Ascending,
+        };
+        self.container_sort = (column, order);
+        self.selected_row = 0;
+    }
+    
     pub fn should_update(&self, refresh_rate: Duration) -> bool {
         self.automatic_refresh && self.last_update.elapsed() >= refresh_rate
     }
@@ -139,6 +272,8 @@ pub fn handle_key_event(key_event: KeyEvent, state: &mut UiState) -> bool {
         return true; // UI changed
     }
     
+    let previous_view = state.views.current();
+    
     match key_event.code {
         // Navigation
         KeyCode::Tab => state.views.next(),
@@ -147,10 +282,27 @@ pub fn handle_key_event(key_event: KeyEvent, state: &mut UiState) -> bool {
         KeyCode::Char('2') => state.views.go_to(ViewType::CpuDetailed),
         KeyCode::Char('3') => state.views.go_to(ViewType::MemoryDetailed),
         KeyCode::Char('4') => state.views.go_to(ViewType::GpuDetailed),
+        KeyCode::Char('5') => state.views.go_to(ViewType::DiskDetailed),
+        KeyCode::Char('6') => state.views.go_to(ViewType::Sensors),
+        KeyCode::Char('7') => state.views.go_to(ViewType::Pressure),
+        KeyCode::Char('8') => state.views.go_to(ViewType::Containers),
+        KeyCode::Char('9') => state.views.go_to(ViewType::Power),
+        KeyCode::Char('0') => state.views.go_to(ViewType::Numa),
+        KeyCode::Char('m') => state.views.go_to(ViewType::Compare),
         KeyCode::Char('?') | KeyCode::Char('h') => state.views.go_to(ViewType::Help),
         
         // Controls
         KeyCode::Char('p') => state.toggle_automatic_refresh(),
+        KeyCode::Char('g') => state.toggle_cgroup_perspective(),
+        KeyCode::Char('c') => state.toggle_core_history(),
+        KeyCode::Char('d') => state.toggle_histogram(),
+        KeyCode::Char('+') | KeyCode::Char('=') => state.zoom_in(),
+        KeyCode::Char('-') => state.zoom_out(),
+        KeyCode::Up => state.select_previous(),
+        KeyCode::Down => state.select_next(),
+        KeyCode::Char(' ') if state.views.current() == ViewType::Compare => state.toggle_selected_series(),
+        KeyCode::Char('s') if state.views.current() == ViewType::Containers => state.next_sort_column(),
+        KeyCode::Char('S') if state.views.current() == ViewType::Containers => state.reverse_sort(),
         KeyCode::Char('r') => {
             state.mark_updated();
             return true; // Force refresh
@@ -159,6 +311,12 @@ pub fn handle_key_event(key_event: KeyEvent, state: &mut UiState) -> bool {
         _ => return false, // No UI change needed
     }
     
+    // Each view starts as itself with its first table row selected
+    if state.views.current() != previous_view {
+        state.selected_row = 0;
+        state.show_histogram = false;
+    }
+    
     true // UI changed, need to redraw
 }
 
@@ -222,7 +380,7 @@ pub fn draw_content_box<W: Write>(
 }
 
 // Draw common UI elements like titlebar and help line
-pub fn draw_ui_frame<W: Write>(stdout: &mut W, state: &UiState) -> io::Result<()> {
+pub fn draw_ui_frame<W: Write>(stdout: &mut W, state: &UiState, host: &HostInfo, power: &PowerStatus) -> io::Result<()> {
     let view_name = state.views.current().name();
     
     // Get terminal dimensions
@@ -257,7 +415,7 @@ pub fn draw_ui_frame<W: Write>(stdout: &mut W, state: &UiState) -> io::Result<()
     
     // View title - centered
     let view_title = format!(" {} ", view_name);
-    let center_pos = (term_width as usize - view_title.len()) / 2;
+    let center_pos = (term_width as usize).saturating_sub(view_title.len()) / 2;
     execute!(
         stdout,
         MoveTo(center_pos as u16, 0),
@@ -287,9 +445,51 @@ pub fn draw_ui_frame<W: Write>(stdout: &mut W, state: &UiState) -> io::Result<()
         ResetColor
     )?;
     
+    // Perspective indicator when usage is relative to cgroup limits
+    if state.cgroup_perspective {
+        let perspective = " CGROUP ";
+        execute!(
+            stdout,
+            MoveTo(term_width - status.len() as u16 - perspective.len() as u16 - 3, 0),
+            SetForegroundColor(Color::Magenta),
+            Print(perspective),
+            ResetColor
+        )?;
+    }
+    
+    // Host information line so screenshots identify the machine
+    let users = match host.logged_in_users {
+        Some(count) => format!(" | users: {}", count),
+        None => String::new(),
+    };
+    let battery = match power.summary() {
+        Some(summary) => format!(" | {}", summary),
+        None => String::new(),
+    };
+    let host_line = format!(
+        " {} | kernel {} | up {} | load {:.2} {:.2} {:.2}{}{}",
+        host.hostname,
+        host.kernel_version,
+        host.format_uptime(),
+        host.load_average.0,
+        host.load_average.1,
+        host.load_average.2,
+        users,
+        battery
+    );
+    let host_line: String = host_line.chars().take(term_width as usize).collect();
+    
+    execute!(
+        stdout,
+        MoveTo(0, 1),
+        SetForegroundColor(Color::DarkGrey),
+        Print(host_line),
+        ResetColor
+    )?;
+    
     // Help line at the bottom
     if state.show_help_line {
-        let help_text = " [?] Help | [Tab] Next view | [1-4] Switch view | [p] Pause/resume | [r] Refresh | [q] Quit ";
+        let help_text = " [?] Help | [Tab] Next view | [0-9] Switch view | [p] Pause/resume | [r] Refresh | [q] Quit ";
         
         execute!(
             stdout,
@@ -309,6 +509,8 @@ pub fn draw_overview_view<W: Write>(
     cpu_usage: f32,
     memory_usage: f32,
     gpu_info: &[GpuInfo],
+    history: &HistoryStore,
+    (cpu_metric, mem_metric): (Metric, Metric),
 ) -> io::Result<()> {
     // Get terminal dimensions to properly size content
     let (term_width, term_height) = match crossterm::terminal::size() {
@@ -318,7 +520,9 @@ pub fn draw_overview_view<W: Write>(
     
     // Calculate content box dimensions (leaving room for borders)
     let content_width = term_width.saturating_sub(4);
-    let bar_width = content_width.saturating_sub(25); // Allow space for labels and values
+    let spark_width = sparkline_width(content_width);
+    let bar_width = content_width.saturating_sub(26 + spark_width); // Allow space for labels, values and history
+    let spark_x = (2 + content_width.saturating_sub(spark_width)) as u16;
     
     // Create a content area with a border
     draw_content_box(stdout, "System Overview", 2, term_height as u16 - 3)?;
@@ -332,12 +536,15 @@ pub fn draw_overview_view<W: Write>(
     execute!(stdout, MoveTo(content_start_x, current_row))?;
     let cpu_chart = BarChart::new("CPU Usage", cpu_usage, bar_width);
     cpu_chart.draw(stdout)?;
+    draw_history_sparkline(stdout, history, cpu_metric, thresholds().usage, spark_x, current_row, spark_width)?;
     current_row += 2;
     
     // Draw memory usage
     execute!(stdout, MoveTo(content_start_x, current_row))?;
-    let mem_chart = BarChart::new("Memory Usage", memory_usage, bar_width);
+    let mem_chart = BarChart::new("Memory Usage", memory_usage, bar_width)
+        .with_thresholds(thresholds().memory);
     mem_chart.draw(stdout)?;
+    draw_history_sparkline(stdout, history, mem_metric, thresholds().memory, spark_x, current_row, spark_width)?;
     current_row += 2;
     
     // Draw GPU usage if available
@@ -346,20 +553,104 @@ pub fn draw_overview_view<W: Write>(
             execute!(stdout, MoveTo(content_start_x, current_row))?;
             let gpu_usage_chart = BarChart::new(&format!("GPU #{} Usage", i), gpu.utilization, bar_width);
             gpu_usage_chart.draw(stdout)?;
+            draw_history_sparkline(stdout, history, Metric::GpuUtilization(i), thresholds().usage, spark_x, current_row, spark_width)?;
             current_row += 1;
             
-            execute!(stdout, MoveTo(content_start_x, current_row))?;
-            let gpu_mem_chart = BarChart::new(&format!("GPU #{} Memory", i), gpu.memory_usage, bar_width);
-            gpu_mem_chart.draw(stdout)?;
-            current_row += 2;
+            // Integrated GPUs share system memory and have no separate figure
+            if gpu.total_memory > 0 {
+                execute!(stdout, MoveTo(content_start_x, current_row))?;
+                let gpu_mem_chart = BarChart::new(&format!("GPU #{} Memory", i), gpu.memory_usage, bar_width)
+                    .with_thresholds(thresholds().gpu_memory);
+                gpu_mem_chart.draw(stdout)?;
+                draw_history_sparkline(stdout, history, Metric::GpuMemory(i), thresholds().gpu_memory, spark_x, current_row, spark_width)?;
+                current_row += 1;
+            }
+            current_row += 1;
         }
     }
     
     Ok(())
 }
 
+// Format a chart window as e.g. "15m" or "24h"
+fn format_window(window: Duration) -> String {
+    let secs = window.as_secs();
+    if secs >= 3600 {
+        format!("{}h", secs / 3600)
+    } else {
+        format!("{}m", secs / 60)
+    }
+}
+
+// Points of a metric over a chart window, one per sample or, for long
+// windows, one per rollup bucket at its average
+fn chart_points(history: &HistoryStore, metric: Metric, window: Duration) -> Vec<(f32, f32)> {
+    let now = Instant::now();
+    history
+        .rollups(metric, window)
+        .iter()
+        .map(|rollup| (now.saturating_duration_since(rollup.start).as_secs_f32(), rollup.average()))
+        .collect()
+}
+
+// Width of the sparkline column to the right of the bars
+fn sparkline_width(content_width: usize) -> usize {
+    (content_width / 5).clamp(8, 30)
+}
+
+// Draw a metric's recent history as a sparkline at the given position, colored
+// with the same thresholds as the bar beside it
+fn draw_history_sparkline<W: Write>(
+    stdout: &mut W,
+    history: &HistoryStore,
+    metric: Metric,
+    thresholds: Thresholds,
+    x: u16,
+    row: u16,
+    width: usize,
+) -> io::Result<()> {
+    execute!(stdout, MoveTo(x, row))?;
+    Sparkline::new(&history.recent(metric, SPARKLINE_WINDOW), width)
+        .with_max(100.0)
+        .with_thresholds(thresholds)
+        .draw(stdout)
+}
+
+// Build the segments for a CPU time breakdown
+fn cpu_breakdown_segments(breakdown: &CpuBreakdown) -> Vec<BarSegment> {
+    [
+        ("user", breakdown.user, Color::Green),
+        ("nice", breakdown.nice, Color::Blue),
+        ("system", breakdown.system, Color::Red),
+        ("iowait", breakdown.iowait, Color::Magenta),
+        ("irq", breakdown.irq, Color::Yellow),
+        ("steal", breakdown.steal, Color::Cyan),
+    ]
+    .iter()
+    .map(|(label, value, color)| BarSegment::new(format!("{} {:.1}%", label, value), *value, *color))
+    .collect()
+}
+
 // Draw CPU-specific view with detailed information
-pub fn draw_cpu_view<W: Write>(stdout: &mut W, cpu_overall: f32, cpu_per_core: &[f32]) -> io::Result<()> {
+pub fn draw_cpu_view<W: Write>(
+    stdout: &mut W,
+    cpu_overall: f32,
+    cpu_per_core: &[f32],
+    cpu_monitor: &CpuMonitor,
+    cgroup: Option<&CgroupLimits>,
+    history: &HistoryStore,
+    core_history: bool,
+) -> io::Result<()> {
+    let details = cpu_monitor.get_details();
+    let total_breakdown = cpu_monitor.get_total_breakdown();
+    let core_breakdown = cpu_monitor.get_core_breakdown();
+    
+    // In cgroup perspective only the CPUs in the cgroup's cpuset are shown
+    let visible_cpus = cgroup
+        .map(|limits| limits.cpuset.as_slice())
+        .filter(|cpuset| !cpuset.is_empty());
+    let cpu_metric = if cgroup.is_some() { Metric::CgroupCpu } else { Metric::CpuOverall };
+    
     // Get terminal dimensions to properly size content
     let (term_width, term_height) = match crossterm::terminal::size() {
         Ok((w, h)) => (w as usize, h as usize),
@@ -368,7 +659,10 @@ pub fn draw_cpu_view<W: Write>(stdout: &mut W, cpu_overall: f32, cpu_per_core: &
     
     // Calculate content box dimensions
     let content_width = term_width.saturating_sub(4);
-    let bar_width = content_width.saturating_sub(25); // Allow space for labels and values
+    let spark_width = sparkline_width(content_width);
+    let spark_x = (2 + content_width.saturating_sub(spark_width)) as u16;
+    let bar_width = content_width.saturating_sub(26 + spark_width); // Allow space for labels, values and history
+    let core_bar_width = content_width.saturating_sub(36 + spark_width); // Cores also show their frequency
     
     // Create a content area with a border
     draw_content_box(stdout, "CPU Details", 2, term_height as u16 - 3)?;
@@ -378,17 +672,135 @@ pub fn draw_cpu_view<W: Write>(stdout: &mut W, cpu_overall: f32, cpu_per_core: &
     let content_start_y = 3;
     let mut current_row = content_start_y;
     
+    // CPU model and topology summary
+    execute!(
+        stdout,
+        MoveTo(content_start_x, current_row),
+        SetForegroundColor(Color::White),
+        Print(format!("{} ({})", details.brand, details.vendor)),
+        ResetColor
+    )?;
+    current_row += 1;
+    
+    let physical = match details.physical_cores {
+        Some(count) => count.to_string(),
+        None => String::from("?"),
+    };
+    let mut summary = format!(
+        "{} socket(s), {} physical cores, {} logical CPUs",
+        details.socket_count(),
+        physical,
+        details.logical_cpus.len()
+    );
+    if let Some((min, max)) = details.frequency_range() {
+        summary.push_str(&format!(" | {}-{} MHz", min, max));
+    }
+    if let Some(governor) = details.governor() {
+        summary.push_str(&format!(" | governor: {}", governor));
+    }
+    execute!(
+        stdout,
+        MoveTo(content_start_x, current_row),
+        SetForegroundColor(Color::DarkGrey),
+        Print(summary),
+        ResetColor
+    )?;
+    current_row += 2;
+    
     // Draw overall CPU usage
     execute!(stdout, MoveTo(content_start_x, current_row))?;
     let cpu_chart = BarChart::new("Overall CPU", cpu_overall, bar_width);
     cpu_chart.draw(stdout)?;
-    current_row += 2; // Add some spacing
+    draw_history_sparkline(stdout, history, cpu_metric, thresholds().usage, spark_x, current_row, spark_width)?;
+    current_row += 1;
     
-    // Draw individual core bar charts
-    for (i, usage) in cpu_per_core.iter().enumerate() {
-        execute!(stdout, MoveTo(content_start_x, current_row))?;
-        let core_chart = BarChart::new(&format!("Core #{}", i), *usage, bar_width);
-        core_chart.draw(stdout)?;
+    // Draw where the overall CPU time went, when /proc/stat is available
+    if let Some(breakdown) = total_breakdown {
+        let breakdown_bar = StackedBar::new(
+            content_start_x,
+            current_row,
+            "Time Breakdown",
+            cpu_breakdown_segments(breakdown),
+            100.0,
+            bar_width,
+        )
+        .with_value_label(format!("{:5.1}%", breakdown.busy()))
+        .with_legend(content_width);
+        breakdown_bar.draw(stdout)?;
+        current_row += breakdown_bar.height();
+    }
+    current_row += 1; // Add some spacing
+    
+    // Cores in topology order: grouped by socket with hyperthread siblings
+    // next to each other
+    let cores: Vec<usize> = details
+        .topology_order()
+        .into_iter()
+        .filter(|i| visible_cpus.map(|visible| visible.contains(&details.logical_cpus[*i].id)).unwrap_or(true))
+        .collect();
+    let multi_socket = details.socket_count() > 1;
+    
+    // Many-core machines get a heatmap when one bar per core would not fit
+    let box_bottom = term_height as u16 - 3;
+    let rows_left = box_bottom.saturating_sub(current_row) as usize;
+    let socket_rows = if multi_socket { details.socket_count() } else { 0 };
+    if cores.len() + socket_rows > rows_left {
+        let cells = cores
+            .iter()
+            .map(|i| {
+                let current = cpu_per_core.get(*i).copied().unwrap_or(0.0);
+                let values = if core_history {
+                    history.recent(Metric::CpuCore(*i), SPARKLINE_WINDOW)
+                } else {
+                    vec![current]
+                };
+                HeatmapCell {
+                    label: details.logical_cpus[*i].id.to_string(),
+                    values,
+                }
+            })
+            .collect();
+        
+        Heatmap::new(content_start_x, current_row, content_width, rows_left, cells)
+            .with_history(core_history)
+            .draw(stdout)?;
+        return Ok(());
+    }
+    
+    // Otherwise draw individual core bar charts
+    let mut last_package = None;
+    
+    for i in cores {
+        let cpu = &details.logical_cpus[i];
+        let usage = cpu_per_core.get(i).copied().unwrap_or(0.0);
+        
+        if multi_socket && last_package != Some(cpu.package_id) {
+            execute!(
+                stdout,
+                MoveTo(content_start_x, current_row),
+                SetForegroundColor(Color::Green),
+                Print(format!("=== Socket {} ===", cpu.package_id)),
+                ResetColor
+            )?;
+            current_row += 1;
+            last_package = Some(cpu.package_id);
+        }
+        
+        let title = format!("Core {} #{}", cpu.core_id, cpu.id);
+        let value_text = format!("{:5.1}% {:>5} MHz", usage, cpu.current_freq_mhz);
+        
+        // Prefer a segmented bar so iowait and steal stand out from real work
+        if let Some(breakdown) = core_breakdown.get(i) {
+            StackedBar::new(content_start_x, current_row, &title, cpu_breakdown_segments(breakdown), 100.0, core_bar_width)
+                .with_value_label(value_text)
+                .draw(stdout)?;
+        } else {
+            execute!(stdout, MoveTo(content_start_x, current_row))?;
+            let core_chart = BarChart::new(&title, usage, core_bar_width)
+                .with_value_label(value_text);
+            core_chart.draw(stdout)?;
+        }
+        draw_history_sparkline(stdout, history, Metric::CpuCore(i), thresholds().usage, spark_x, current_row, spark_width)?;
         current_row += 1; // Each core on its own row
     }
     
@@ -398,9 +810,8 @@ pub fn draw_cpu_view<W: Write>(stdout: &mut W, cpu_overall: f32, cpu_per_core: &
 // Draw memory-specific view
 pub fn draw_memory_view<W: Write>(
     stdout: &mut W, 
-    total_mem: u64, 
-    used_mem: u64, 
-    mem_usage: f32
+    mem_info: &MemInfo,
+    cgroup: Option<&CgroupLimits>,
 ) -> io::Result<()> {
     // Get terminal dimensions to properly size content
     let (term_width, term_height) = match crossterm::terminal::size() {
@@ -430,53 +841,99 @@ pub fn draw_memory_view<W: Write>(
     )?;
     current_row += 1;
     
-    // Format memory values with consistent alignment
-    execute!(
-        stdout,
-        MoveTo(content_start_x, current_row),
-        Print(format!("┌{:─^40}┐", "")),
-    )?;
-    current_row += 1;
+    let memory_rows = [
+        ("Total Memory:", format!("{} MB", mem_info.total)),
+        ("Used Memory:", format!("{} MB", mem_info.used())),
+        ("Free Memory:", format!("{} MB", mem_info.free)),
+        ("Available Memory:", format!("{} MB", mem_info.available)),
+        ("Buffers:", format!("{} MB", mem_info.buffers)),
+        ("Page Cache:", format!("{} MB", mem_info.cached)),
+        ("Shared:", format!("{} MB", mem_info.shared)),
+        ("Slab:", format!("{} MB", mem_info.slab)),
+        ("Dirty:", format!("{} MB", mem_info.dirty)),
+        ("Usage Percentage:", format!("{:.1}%", mem_info.used_percent())),
+    ];
     
-    execute!(
-        stdout,
-        MoveTo(content_start_x, current_row),
-        Print(format!("│ {:20} │ {:16} │", "Total Memory:", format!("{} MB", total_mem))),
-    )?;
-    current_row += 1;
+    let mut table = Table::new(
+        content_start_x,
+        current_row,
+        content_width,
+        memory_rows.len() + 2,
+        vec![TableColumn::new("Statistic"), TableColumn::new("Value").align(Align::Right).min_width(12)],
+    )
+    .without_header()
+    .bordered();
+    for (label, value) in memory_rows.iter() {
+        table = table.row(vec![TableCell::from(*label), TableCell::from(value.clone())]);
+    }
+    table.draw(stdout)?;
+    current_row += table.height() as u16 + 1;
     
-    execute!(
-        stdout,
-        MoveTo(content_start_x, current_row),
-        Print(format!("│ {:20} │ {:16} │", "Used Memory:", format!("{} MB", used_mem))),
-    )?;
+    // Draw memory usage bar chart
+    execute!(stdout, MoveTo(content_start_x, current_row))?;
+    let mut mem_chart = BarChart::new("Memory Usage", mem_info.used_percent(), bar_width)
+        .with_thresholds(thresholds().memory);
+    // Show where the cgroup's memory.max cuts in on the host's memory
+    if let Some(max) = cgroup.and_then(|limits| limits.memory_max).filter(|max| *max < mem_info.total) {
+        mem_chart = mem_chart.with_limit(max as f32 / mem_info.total as f32 * 100.0);
+    }
+    mem_chart.draw(stdout)?;
     current_row += 1;
     
+    // Draw how the total memory is composed
+    let segments = [
+        ("Used", mem_info.used(), Color::Yellow),
+        ("Buffers", mem_info.buffers, Color::Blue),
+        ("Cache", mem_info.cached + mem_info.slab_reclaimable, Color::Cyan),
+        ("Free", mem_info.free, Color::DarkGrey),
+    ]
+    .iter()
+    .map(|(label, value, color)| BarSegment::new(format!("{} {} MB", label, value), *value as f32, *color))
+    .collect();
+    let composition_bar = StackedBar::new(content_start_x, current_row, "Composition", segments, mem_info.total as f32, bar_width)
+        .with_legend(content_width);
+    composition_bar.draw(stdout)?;
+    current_row += composition_bar.height() + 1;
+    
+    // Swap usage
     execute!(
         stdout,
         MoveTo(content_start_x, current_row),
-        Print(format!("│ {:20} │ {:16} │", "Free Memory:", format!("{} MB", total_mem - used_mem))),
+        Print(format!(
+            "Swap: {} / {} MB used",
+            mem_info.swap_used(),
+            mem_info.swap_total
+        )),
     )?;
     current_row += 1;
     
-    execute!(
-        stdout,
-        MoveTo(content_start_x, current_row),
-        Print(format!("│ {:20} │ {:16} │", "Usage Percentage:", format!("{:.1}%", mem_usage))),
-    )?;
+    if mem_info.swap_total > 0 {
+        execute!(stdout, MoveTo(content_start_x, current_row))?;
+        let swap_chart = BarChart::new("Swap Usage", mem_info.swap_percent(), bar_width)
+            .with_thresholds(thresholds().swap);
+        swap_chart.draw(stdout)?;
+        current_row += 1;
+    }
     current_row += 1;
     
-    execute!(
-        stdout,
-        MoveTo(content_start_x, current_row),
-        Print(format!("└{:─^40}┘", "")),
-    )?;
-    current_row += 2;
-    
-    // Draw memory usage bar chart
-    execute!(stdout, MoveTo(content_start_x, current_row))?;
-    let mem_chart = BarChart::new("Memory Usage", mem_usage, bar_width);
-    mem_chart.draw(stdout)?;
+    // Usage against the cgroup's memory.max in cgroup perspective
+    if let Some(limits) = cgroup {
+        let limit = match limits.memory_max {
+            Some(max) => format!("{} MB", max),
+            None => String::from("unlimited"),
+        };
+        execute!(
+            stdout,
+            MoveTo(content_start_x, current_row),
+            Print(format!("cgroup {}: {} MB used of {}", limits.path, limits.memory_used, limit)),
+        )?;
+        current_row += 1;
+        
+        execute!(stdout, MoveTo(content_start_x, current_row))?;
+        let cgroup_chart = BarChart::new("cgroup Memory", limits.memory_percent(mem_info.total), bar_width)
+            .with_thresholds(thresholds().memory);
+        cgroup_chart.draw(stdout)?;
+    }
     
     Ok(())
 }
@@ -485,6 +942,7 @@ pub fn draw_memory_view<W: Write>(
 pub fn draw_gpu_view<W: Write>(
     stdout: &mut W,
     gpu_info: &[GpuInfo],
+    selected: usize,
 ) -> io::Result<()> {
     // Get terminal dimensions to properly size content
     let (term_width, term_height) = match crossterm::terminal::size() {
@@ -503,6 +961,7 @@ pub fn draw_gpu_view<W: Write>(
     let content_start_x = 2;
     let content_start_y = 3;
     let mut current_row = content_start_y;
+    let last_row = (term_height as u16).saturating_sub(4);
     
     if gpu_info.is_empty() {
         // Show a message if no GPUs are available
@@ -516,120 +975,992 @@ pub fn draw_gpu_view<W: Write>(
         return Ok(());
     }
     
-            // Display GPU information
-    for (i, gpu) in gpu_info.iter().enumerate() {
-        // GPU vendor label
-        let vendor_label = match gpu.vendor {
-            GpuVendor::Nvidia => "NVIDIA GPU",
-            GpuVendor::Apple => "Apple GPU",
-            GpuVendor::Other => "GPU",
-            GpuVendor::None => "Unknown GPU",
+    // With several GPUs, list them all and show the details of the selected one
+    let selected = selected.min(gpu_info.len() - 1);
+    if gpu_info.len() > 1 {
+        let mut table = Table::new(
+            content_start_x,
+            current_row,
+            content_width,
+            (gpu_info.len() + 1).min(last_row.saturating_sub(current_row) as usize / 3).max(3),
+            vec![
+                TableColumn::new("#").align(Align::Right),
+                TableColumn::new("GPU").min_width(8).max_width(40),
+                TableColumn::new("UTIL").align(Align::Right).min_width(6),
+                TableColumn::new("MEMORY").align(Align::Right),
+                TableColumn::new("TEMP").align(Align::Right),
+                TableColumn::new("PROCS").align(Align::Right),
+            ],
+        )
+        .with_selected(selected);
+        for (i, gpu) in gpu_info.iter().enumerate() {
+            let memory = if gpu.total_memory > 0 {
+                TableCell::colored(
+                    format!("{} / {} MB", gpu.used_memory, gpu.total_memory),
+                    thresholds().gpu_memory.color(gpu.memory_usage),
+                )
+            } else {
+                TableCell::from("shared")
+            };
+            let temperature = if gpu.temperature > 0 { format!("{}°C", gpu.temperature) } else { String::from("-") };
+            table = table.row(vec![
+                TableCell::from(i.to_string()),
+                TableCell::from(gpu.name.clone()),
+                TableCell::colored(format!("{:.0}%", gpu.utilization), usage_color(gpu.utilization)),
+                memory,
+                TableCell::from(temperature),
+                TableCell::from(gpu.processes.len().to_string()),
+            ]);
+        }
+        table.draw(stdout)?;
+        current_row += table.height() as u16 + 1;
+    }
+    
+    let (i, gpu) = (selected, &gpu_info[selected]);
+    // GPU vendor label
+    let vendor_label = match gpu.vendor {
+        GpuVendor::Nvidia => "NVIDIA GPU",
+        GpuVendor::Amd => "AMD GPU",
+        GpuVendor::Intel => "Intel GPU",
+        GpuVendor::Apple => "Apple GPU",
+        GpuVendor::Other => "GPU",
+        GpuVendor::None => "Unknown GPU",
+    };
+    
+    execute!(
+        stdout,
+        MoveTo(content_start_x, current_row),
+        SetForegroundColor(Color::Green),
+        Print(format!("=== {} #{} ===", vendor_label, i)),
+        ResetColor
+    )?;
+    current_row += 2;
+    
+    // GPU info table
+    let mut rows: Vec<(&str, String)> = vec![("Name:", gpu.name.clone())];
+    
+    // Only show temperature where the GPU reports one
+    if gpu.temperature > 0 {
+        rows.push(("Temperature:", format!("{}°C", gpu.temperature)));
+    }
+    
+    // Show Apple-specific properties for Apple GPUs
+    if gpu.vendor == GpuVendor::Apple {
+        let gpu_type = if gpu.is_headless { 
+            "Headless" 
+        } else if gpu.is_low_power { 
+            "Integrated/Low Power" 
+        } else { 
+            "Discrete/High Performance" 
         };
-        
+        rows.push(("Type:", gpu_type.to_string()));
+    }
+    
+    rows.push(("Memory:", if gpu.total_memory > 0 {
+        format!("{} MB", gpu.total_memory)
+    } else {
+        String::from("Shared")
+    }));
+    
+    if gpu.vendor != GpuVendor::Apple && gpu.total_memory > 0 {
+        rows.push(("Memory Usage:", format!("{} / {} MB", gpu.used_memory, gpu.total_memory)));
+    }
+    
+    // Power and clocks where the backend reports them
+    let optional_rows = [
+        ("Power Draw:", gpu.power_draw.map(|watts| match gpu.power_limit {
+            Some(limit) => format!("{:.0} / {:.0} W", watts, limit),
+            None => format!("{:.1} W", watts),
+        })),
+        ("Fan Speed:", gpu.fan_speed.map(|pct| format!("{}%", pct))),
+        ("Performance State:", gpu.performance_state.map(|state| format!("P{}", state))),
+        ("Graphics Clock:", gpu.graphics_clock.map(|mhz| match gpu.max_graphics_clock {
+            Some(max) => format!("{} / {} MHz", mhz, max),
+            None => format!("{} MHz", mhz),
+        })),
+        ("SM Clock:", gpu.sm_clock.map(|mhz| format!("{} MHz", mhz))),
+        ("Memory Clock:", gpu.memory_clock.map(|mhz| format!("{} MHz", mhz))),
+        ("Encoder / Decoder:", match (gpu.encoder_utilization, gpu.decoder_utilization) {
+            (Some(enc), Some(dec)) => Some(format!("{}% / {}%", enc, dec)),
+            _ => None,
+        }),
+        ("PCIe RX / TX:", match (gpu.pcie_rx, gpu.pcie_tx) {
+            (Some(rx), Some(tx)) => Some(format!("{:.0} / {:.0} MB/s", rx as f32 / 1024.0, tx as f32 / 1024.0)),
+            _ => None,
+        }),
+        ("ECC Errors:", gpu.ecc_errors.map(|(corrected, uncorrected)| {
+            format!("{} corr / {} unc", corrected, uncorrected)
+        })),
+    ];
+    rows.extend(optional_rows.into_iter().filter_map(|(label, value)| value.map(|value| (label, value))));
+    
+    let mut table = Table::new(
+        content_start_x,
+        current_row,
+        content_width,
+        (rows.len().div_ceil(2) + 2).min(last_row.saturating_sub(current_row) as usize + 1),
+        vec![
+            TableColumn::new("Property"),
+            TableColumn::new("Value").min_width(16),
+            TableColumn::new("Property"),
+            TableColumn::new("Value").min_width(16),
+        ],
+    )
+    .without_header()
+    .bordered();
+    // Properties run in two side-by-side pairs of columns to halve the height
+    for pair in rows.chunks(2) {
+        let mut cells = Vec::new();
+        for (label, value) in pair {
+            cells.push(TableCell::from(*label));
+            cells.push(TableCell::from(value.clone()));
+        }
+        table = table.row(cells);
+    }
+    table.draw(stdout)?;
+    current_row += table.height() as u16;
+    
+    // Active clock throttle reasons, highlighted unless the GPU is just idle
+    if !gpu.throttle_reasons.is_empty() && current_row <= last_row {
+        let throttled = gpu.throttle_reasons.iter().any(|reason| reason != "idle");
         execute!(
             stdout,
             MoveTo(content_start_x, current_row),
-            SetForegroundColor(Color::Green),
-            Print(format!("=== {} #{} ===", vendor_label, i)),
+            SetForegroundColor(if throttled { Color::Yellow } else { Color::DarkGrey }),
+            Print(format!("Throttle: {}", gpu.throttle_reasons.join(", "))),
             ResetColor
         )?;
-        current_row += 2;
-        
-        // GPU info table
-        execute!(
-            stdout,
-            MoveTo(content_start_x, current_row),
-            Print(format!("┌{:─^40}┐", "")),
-        )?;
         current_row += 1;
-        
-        execute!(
-            stdout,
-            MoveTo(content_start_x, current_row),
-            Print(format!("│ {:^40} │", gpu.name)),
-        )?;
+    }
+    current_row += 1;
+    
+    // Stop at the bottom of the content box
+    if current_row > last_row {
+        return Ok(());
+    }
+    
+    // Draw GPU utilization bar chart
+    execute!(stdout, MoveTo(content_start_x, current_row))?;
+    let gpu_util_chart = BarChart::new("GPU Utilization", gpu.utilization, bar_width);
+    gpu_util_chart.draw(stdout)?;
+    current_row += 1;
+    
+    // Draw GPU memory usage bar chart where memory usage is known
+    if gpu.vendor != GpuVendor::Apple && gpu.total_memory > 0 && current_row <= last_row {
+        execute!(stdout, MoveTo(content_start_x, current_row))?;
+        let gpu_mem_chart = BarChart::new("GPU Memory", gpu.memory_usage, bar_width)
+            .with_thresholds(thresholds().gpu_memory);
+        gpu_mem_chart.draw(stdout)?;
         current_row += 1;
         
-        // Only show temperature for NVIDIA GPUs
-        if gpu.vendor == GpuVendor::Nvidia {
-            execute!(
-                stdout,
-                MoveTo(content_start_x, current_row),
-                Print(format!("│ {:20} │ {:16} │", "Temperature:", format!("{}°C", gpu.temperature))),
-            )?;
-            current_row += 1;
-        }
-        
-        // Show Apple-specific properties for Apple GPUs
-        if gpu.vendor == GpuVendor::Apple {
-            let gpu_type = if gpu.is_headless { 
-                "Headless" 
-            } else if gpu.is_low_power { 
-                "Integrated/Low Power" 
-            } else { 
-                "Discrete/High Performance" 
-            };
+        // Which processes hold the memory, largest first, with the rest
+        // (other processes and driver overhead) left unfilled
+        let process_memory: Vec<(&GpuProcess, u64)> = gpu.processes
+            .iter()
+            .filter_map(|process| process.used_memory.map(|mb| (process, mb)))
+            .collect();
+        if !process_memory.is_empty() {
+            let mut segments: Vec<BarSegment> = process_memory
+                .iter()
+                .take(SEGMENT_COLORS.len() - 1)
+                .zip(SEGMENT_COLORS.iter())
+                .map(|((process, mb), color)| {
+                    let name = if process.name.is_empty() { "?" } else { &process.name };
+                    BarSegment::new(format!("{} {} MB", name, mb), *mb as f32, *color)
+                })
+                .collect();
             
-            execute!(
-                stdout,
-                MoveTo(content_start_x, current_row),
-                Print(format!("│ {:20} │ {:16} │", "Type:", gpu_type)),
-            )?;
-            current_row += 1;
-        }
-        
-        execute!(
-            stdout,
-            MoveTo(content_start_x, current_row),
-            Print(format!("│ {:20} │ {:16} │", "Memory:", format!("{} MB", gpu.total_memory))),
-        )?;
-        current_row += 1;
-        
-        if gpu.vendor == GpuVendor::Nvidia {
-            execute!(
-                stdout,
-                MoveTo(content_start_x, current_row),
-                Print(format!("│ {:20} │ {:16} │", "Memory Usage:", format!("{} / {} MB", gpu.used_memory, gpu.total_memory))),
-            )?;
-            current_row += 1;
+            let others = &process_memory[segments.len()..];
+            if !others.is_empty() {
+                let mb: u64 = others.iter().map(|(_, mb)| mb).sum();
+                segments.push(BarSegment::new(
+                    format!("{} others {} MB", others.len(), mb),
+                    mb as f32,
+                    SEGMENT_COLORS[SEGMENT_COLORS.len() - 1],
+                ));
+            }
+            
+            let process_bar = StackedBar::new(
+                content_start_x,
+                current_row,
+                "By Process",
+                segments,
+                gpu.total_memory as f32,
+                bar_width,
+            )
+            .with_legend(content_width);
+            if current_row + process_bar.height() <= last_row + 1 {
+                process_bar.draw(stdout)?;
+                current_row += process_bar.height();
+            }
         }
+    }
+    current_row += 1;
+    
+    // Processes holding contexts on this GPU, largest memory users first
+    if !gpu.processes.is_empty() && current_row < last_row {
+        let mut table = Table::new(
+            content_start_x,
+            current_row,
+            content_width,
+            last_row.saturating_sub(current_row) as usize + 1,
+            vec![
+                TableColumn::new("PID").align(Align::Right).min_width(7),
+                TableColumn::new("USER").min_width(4).max_width(16),
+                TableColumn::new("PROCESS").min_width(7).max_width(32),
+                TableColumn::new("GPU MEM").align(Align::Right),
+                TableColumn::new("SM").align(Align::Right),
+                TableColumn::new("TYPE"),
+            ],
+        )
+        .sorted_by(3, SortOrder::Descending);
         
-        execute!(
-            stdout,
-            MoveTo(content_start_x, current_row),
-            Print(format!("└{:─^40}┘", "")),
-        )?;
-        current_row += 2;
-        
-        // Draw GPU utilization bar chart
-        execute!(stdout, MoveTo(content_start_x, current_row))?;
-        let gpu_util_chart = BarChart::new("GPU Utilization", gpu.utilization, bar_width);
-        gpu_util_chart.draw(stdout)?;
-        current_row += 1;
-        
-        // Draw GPU memory usage bar chart for NVIDIA
-        if gpu.vendor == GpuVendor::Nvidia {
-            execute!(stdout, MoveTo(content_start_x, current_row))?;
-            let gpu_mem_chart = BarChart::new("GPU Memory", gpu.memory_usage, bar_width);
-            gpu_mem_chart.draw(stdout)?;
+        for process in gpu.processes.iter() {
+            let name = if process.name.is_empty() { "?" } else { &process.name };
+            let user = if process.user.is_empty() { "?" } else { &process.user };
+            let memory = process.used_memory
+                .map(|mb| format!("{} MB", mb))
+                .unwrap_or_else(|| String::from("n/a"));
+            let sm = process.sm_utilization
+                .map(|pct| format!("{}%", pct))
+                .unwrap_or_else(|| String::from("-"));
+            let kind = if process.is_graphics { "graphics" } else { "compute" };
+            
+            table = table.row(vec![
+                TableCell::from(process.pid.to_string()),
+                TableCell::from(user),
+                TableCell::from(name),
+                TableCell::from(memory).with_sort_key(process.used_memory.map(|mb| mb as f64).unwrap_or(-1.0)),
+                TableCell::from(sm).with_sort_key(process.sm_utilization.map(|pct| pct as f64).unwrap_or(-1.0)),
+                TableCell::from(kind),
+            ]);
         }
-        
-        current_row += 2;
+        table.draw(stdout)?;
     }
     
     Ok(())
 }
 
-// Draw view for when no GPU is available
-pub fn draw_no_gpu_view<W: Write>(stdout: &mut W) -> io::Result<()> {
+// Format a byte rate with a human-readable unit
+fn format_rate(bytes_per_sec: f64) -> String {
+    const UNITS: [&str; 4] = ["B/s", "KB/s", "MB/s", "GB/s"];
+    let mut value = bytes_per_sec;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", value, UNITS[unit])
+}
+
+// Draw disk I/O view with per-device throughput and latency
+pub fn draw_disk_view<W: Write>(
+    stdout: &mut W,
+    disks: &[DiskInfo],
+) -> io::Result<()> {
     // Get terminal dimensions to properly size content
     let (term_width, term_height) = match crossterm::terminal::size() {
         Ok((w, h)) => (w as usize, h as usize),
         Err(_) => (80, 24), // Fallback to a reasonable default
     };
     
-    // Create a content area with a border
-    draw_content_box(stdout, "GPU Details", 2, term_height as u16 - 3)?;
+    // Calculate content box dimensions
+    let content_width = term_width.saturating_sub(4);
+    let bar_width = content_width.saturating_sub(25); // Allow space for labels and values
+    
+    // Create a content area with a border
+    draw_content_box(stdout, "Disk I/O", 2, term_height as u16 - 3)?;
+    
+    // Start content 1 row below the header, 2 columns in from the left
+    let content_start_x = 2;
+    let content_start_y = 3;
+    let mut current_row = content_start_y;
+    let last_row = (term_height as u16).saturating_sub(4);
+    
+    if disks.is_empty() {
+        execute!(
+            stdout,
+            MoveTo(content_start_x, current_row),
+            Print("No block device statistics available."),
+            MoveTo(content_start_x, current_row + 1),
+            Print("Disk I/O monitoring requires /proc/diskstats (Linux).")
+        )?;
+        return Ok(());
+    }
+    
+    // One row per device, leaving at least half the box for the utilization bars
+    let mut table = Table::new(
+        content_start_x,
+        current_row,
+        content_width,
+        (disks.len() + 1).min(last_row.saturating_sub(current_row) as usize / 2).max(3),
+        vec![
+            TableColumn::new("DEVICE").min_width(6).max_width(16),
+            TableColumn::new("READ").align(Align::Right).min_width(10),
+            TableColumn::new("R IOPS").align(Align::Right),
+            TableColumn::new("WRITE").align(Align::Right).min_width(10),
+            TableColumn::new("W IOPS").align(Align::Right),
+            TableColumn::new("WAIT").align(Align::Right),
+            TableColumn::new("QUEUE").align(Align::Right),
+            TableColumn::new("UTIL").align(Align::Right).min_width(6),
+        ],
+    );
+    for disk in disks {
+        table = table.row(vec![
+            TableCell::from(disk.name.clone()),
+            TableCell::from(format_rate(disk.read_bytes_per_sec)),
+            TableCell::from(format!("{:.1}", disk.read_iops)),
+            TableCell::from(format_rate(disk.write_bytes_per_sec)),
+            TableCell::from(format!("{:.1}", disk.write_iops)),
+            TableCell::from(format!("{:.1} ms", disk.avg_wait_ms)),
+            TableCell::from(disk.in_flight.to_string()),
+            TableCell::colored(format!("{:.1}%", disk.utilization), usage_color(disk.utilization)),
+        ]);
+    }
+    let table_height = table.height() as u16;
+    table.draw(stdout)?;
+    current_row += table_height + 1;
+    
+    if current_row + 1 > last_row {
+        return Ok(());
+    }
+    
+    execute!(
+        stdout,
+        MoveTo(content_start_x, current_row),
+        SetForegroundColor(Color::Green),
+        Print("=== Utilization ==="),
+        ResetColor
+    )?;
+    current_row += 1;
+    
+    for disk in disks {
+        if current_row > last_row {
+            break;
+        }
+        
+        let title: String = disk.name.chars().take(14).collect();
+        execute!(stdout, MoveTo(content_start_x, current_row))?;
+        let util_chart = BarChart::new(&title, disk.utilization, bar_width);
+        util_chart.draw(stdout)?;
+        current_row += 1;
+    }
+    
+    Ok(())
+}
+
+// Draw temperature sensors grouped by hardware category
+pub fn draw_sensors_view<W: Write>(
+    stdout: &mut W,
+    readings: &[SensorReading],
+) -> io::Result<()> {
+    // Get terminal dimensions to properly size content
+    let (term_width, term_height) = match crossterm::terminal::size() {
+        Ok((w, h)) => (w as usize, h as usize),
+        Err(_) => (80, 24), // Fallback to a reasonable default
+    };
+    
+    // Calculate content box dimensions
+    let content_width = term_width.saturating_sub(4);
+    let bar_width = content_width.saturating_sub(40); // Allow space for labels and thresholds
+    
+    // Create a content area with a border
+    draw_content_box(stdout, "Temperature Sensors", 2, term_height as u16 - 3)?;
+    
+    // Start content 1 row below the header, 2 columns in from the left
+    let content_start_x = 2;
+    let content_start_y = 3;
+    let mut current_row = content_start_y;
+    let last_row = term_height as u16 - 4;
+    
+    if readings.is_empty() {
+        execute!(
+            stdout,
+            MoveTo(content_start_x, current_row),
+            Print("No temperature sensors detected."),
+            MoveTo(content_start_x, current_row + 1),
+            Print("Sensors are read from /sys/class/hwmon or the platform sensor API.")
+        )?;
+        return Ok(());
+    }
+    
+    let categories = [
+        SensorKind::Cpu,
+        SensorKind::Nvme,
+        SensorKind::Chipset,
+        SensorKind::Gpu,
+        SensorKind::Other,
+    ];
+    
+    for kind in categories.iter() {
+        let group: Vec<&SensorReading> = readings.iter().filter(|r| r.kind == *kind).collect();
+        if group.is_empty() || current_row > last_row {
+            continue;
+        }
+        
+        execute!(
+            stdout,
+            MoveTo(content_start_x, current_row),
+            SetForegroundColor(Color::Green),
+            Print(format!("=== {} ===", kind.name())),
+            ResetColor
+        )?;
+        current_row += 1;
+        
+        for reading in group {
+            if current_row > last_row {
+                break;
+            }
+            
+            let value_label = match reading.critical {
+                Some(critical) => format!("{:.1}°C (crit {:.0}°C)", reading.temperature, critical),
+                None => format!("{:.1}°C", reading.temperature),
+            };
+            let title: String = reading.display_name().chars().take(14).collect();
+            
+            execute!(stdout, MoveTo(content_start_x, current_row))?;
+            let temp_chart = BarChart::new(&title, reading.percent_of_critical(), bar_width)
+                .with_value_label(value_label)
+                .with_thresholds(thresholds().temperature)
+                .gradient();
+            temp_chart.draw(stdout)?;
+            current_row += 1;
+        }
+        
+        current_row += 1;
+    }
+    
+    Ok(())
+}
+
+// Rows draw_pressure_rows takes for one pressure reading
+fn pressure_row_count(pressure: &Pressure) -> u16 {
+    if pressure.full.is_some() { 2 } else { 1 }
+}
+
+// Draw the some/full rows for one pressure reading, returning the next free row
+fn draw_pressure_rows<W: Write>(
+    stdout: &mut W,
+    pressure: &Pressure,
+    x: u16,
+    mut row: u16,
+    bar_width: usize,
+) -> io::Result<u16> {
+    let lines = [("some", Some(&pressure.some)), ("full", pressure.full.as_ref())];
+    
+    for (label, averages) in lines.iter() {
+        if let Some(averages) = averages {
+            execute!(stdout, MoveTo(x, row))?;
+            let chart = BarChart::new(&format!("  {} avg10", label), averages.avg10, bar_width)
+                .with_thresholds(thresholds().pressure)
+                .with_value_label(format!(
+                    "{:6.2}%  avg60 {:6.2}%  avg300 {:6.2}%  total {:.1}s",
+                    averages.avg10,
+                    averages.avg60,
+                    averages.avg300,
+                    averages.total_us as f64 / 1_000_000.0
+                ));
+            chart.draw(stdout)?;
+            row += 1;
+        }
+    }
+    
+    Ok(row)
+}
+
+// Draw Pressure Stall Information for the system and the current cgroup
+pub fn draw_psi_view<W: Write>(
+    stdout: &mut W,
+    psi: &PsiMonitor,
+    history: &HistoryStore,
+    window: Duration,
+) -> io::Result<()> {
+    // Get terminal dimensions to properly size content
+    let (term_width, term_height) = match crossterm::terminal::size() {
+        Ok((w, h)) => (w as usize, h as usize),
+        Err(_) => (80, 24), // Fallback to a reasonable default
+    };
+    
+    // Calculate content box dimensions
+    let content_width = term_width.saturating_sub(4);
+    let bar_width = content_width.saturating_sub(70); // Allow space for labels and all three averages
+    
+    // Create a content area with a border
+    draw_content_box(stdout, "Pressure Stall Information", 2, term_height as u16 - 3)?;
+    
+    // Start content 1 row below the header, 2 columns in from the left
+    let content_start_x = 2;
+    let content_start_y = 3;
+    let mut current_row = content_start_y;
+    let box_bottom = term_height as u16 - 3;
+    let last_row = box_bottom - 1;
+    
+    let system = psi.get_system();
+    if system.is_empty() {
+        execute!(
+            stdout,
+            MoveTo(content_start_x, current_row),
+            Print("Pressure Stall Information is not available."),
+            MoveTo(content_start_x, current_row + 1),
+            Print("PSI requires Linux 4.20+ with CONFIG_PSI enabled (see /proc/pressure).")
+        )?;
+        return Ok(());
+    }
+    
+    for resource in PsiResource::ALL.iter() {
+        let pressure = match system.get(*resource) {
+            Some(pressure) => pressure,
+            None => continue,
+        };
+        
+        // Stop at the bottom of the content box
+        if current_row + pressure_row_count(pressure) > last_row {
+            break;
+        }
+        
+        execute!(
+            stdout,
+            MoveTo(content_start_x, current_row),
+            SetForegroundColor(Color::Green),
+            Print(format!("=== {} pressure ===", resource.name())),
+            ResetColor
+        )?;
+        current_row += 1;
+        
+        current_row = draw_pressure_rows(stdout, pressure, content_start_x, current_row, bar_width)?;
+        current_row += 1;
+    }
+    
+    // Rows the cgroup section needs, kept free below the chart
+    let cgroup_rows = psi
+        .get_cgroup()
+        .map(|(_, cgroup)| {
+            let resources: u16 = PsiResource::ALL
+                .iter()
+                .filter_map(|resource| cgroup.get(*resource))
+                .map(|pressure| 1 + pressure_row_count(pressure))
+                .sum();
+            1 + resources
+        })
+        .unwrap_or(0);
+    
+    // Recent "some" avg10 of every resource on one chart, when there is room;
+    // the chart ends just above chart_bottom, which stays blank
+    let chart_limit = if cgroup_rows > 0 { box_bottom.saturating_sub(cgroup_rows + 1) } else { box_bottom };
+    if chart_limit.saturating_sub(current_row) >= 6 {
+        let chart_bottom = chart_limit.min(current_row + 14);
+        let mut chart = LineChart::within_content_box(current_row, chart_bottom, window)
+            .with_unit("%");
+        let colors = [Color::Cyan, Color::Magenta, Color::Yellow];
+        for (resource, color) in PsiResource::ALL.iter().zip(colors.iter()) {
+            chart = chart.with_series(ChartSeries {
+                label: format!("{} some avg10", resource.name()),
+                color: *color,
+                points: chart_points(history, Metric::Pressure(*resource), window),
+            });
+        }
+        chart.draw(stdout)?;
+        current_row = chart_bottom + 1;
+    }
+    
+    // Pressure inside the cgroup ezstats runs in, e.g. a container or systemd slice
+    if let Some((path, cgroup)) = psi.get_cgroup().filter(|_| current_row <= last_row) {
+        execute!(
+            stdout,
+            MoveTo(content_start_x, current_row),
+            SetForegroundColor(Color::Green),
+            Print(format!("=== cgroup {} ===", path)),
+            ResetColor
+        )?;
+        current_row += 1;
+        
+        for resource in PsiResource::ALL.iter() {
+            if let Some(pressure) = cgroup.get(*resource) {
+                if current_row + pressure_row_count(pressure) > last_row {
+                    break;
+                }
+                execute!(
+                    stdout,
+                    MoveTo(content_start_x, current_row),
+                    Print(resource.name())
+                )?;
+                current_row += 1;
+                current_row = draw_pressure_rows(stdout, pressure, content_start_x, current_row, bar_width)?;
+            }
+        }
+    }
+    
+    Ok(())
+}
+
+// Columns of the containers table, which can be sorted by any of them
+fn container_columns() -> Vec<TableColumn> {
+    vec![
+        TableColumn::new("CONTAINER").min_width(12),
+        TableColumn::new("RUNTIME"),
+        TableColumn::new("MAIN PROCESS").min_width(8).max_width(24),
+        TableColumn::new("PROCS").align(Align::Right),
+        TableColumn::new("CPU %").align(Align::Right).min_width(6),
+        TableColumn::new("MEMORY").align(Align::Right),
+        TableColumn::new("READ").align(Align::Right).min_width(10),
+        TableColumn::new("WRITE").align(Align::Right).min_width(10),
+    ]
+}
+
+// Draw containers found from process cgroups with their resource usage
+pub fn draw_containers_view<W: Write>(
+    stdout: &mut W,
+    containers: &[ContainerInfo],
+    selected: usize,
+    sort: (usize, SortOrder),
+) -> io::Result<()> {
+    // Get terminal dimensions to properly size content
+    let (term_width, term_height) = match crossterm::terminal::size() {
+        Ok((w, h)) => (w as usize, h as usize),
+        Err(_) => (80, 24), // Fallback to a reasonable default
+    };
+    let content_width = term_width.saturating_sub(4);
+    
+    // Create a content area with a border
+    draw_content_box(stdout, "Containers", 2, term_height as u16 - 3)?;
+    
+    // Start content 1 row below the header, 2 columns in from the left
+    let content_start_x = 2;
+    let content_start_y = 3;
+    let current_row = content_start_y;
+    let last_row = term_height as u16 - 4;
+    
+    if containers.is_empty() {
+        execute!(
+            stdout,
+            MoveTo(content_start_x, current_row),
+            Print("No containers detected."),
+            MoveTo(content_start_x, current_row + 1),
+            Print("Containers are found from process cgroups (cgroup v2) without a container daemon.")
+        )?;
+        return Ok(());
+    }
+    
+    let (sort_column, sort_order) = sort;
+    let mut table = Table::new(
+        content_start_x,
+        current_row,
+        content_width,
+        last_row.saturating_sub(current_row) as usize + 1,
+        container_columns(),
+    )
+    .sorted_by(sort_column, sort_order)
+    .with_selected(selected.min(containers.len() - 1));
+    
+    for info in containers {
+        table = table.row(vec![
+            TableCell::from(info.container.short_id()),
+            TableCell::from(info.container.runtime),
+            TableCell::from(info.main_process.clone()),
+            TableCell::from(info.process_count.to_string()).with_sort_key(info.process_count as f64),
+            TableCell::colored(format!("{:.1}%", info.cpu_percent), usage_color(info.cpu_percent))
+                .with_sort_key(info.cpu_percent as f64),
+            TableCell::from(format!("{} MB", info.memory_mb)).with_sort_key(info.memory_mb as f64),
+            TableCell::from(format_rate(info.read_bytes_per_sec)).with_sort_key(info.read_bytes_per_sec),
+            TableCell::from(format_rate(info.write_bytes_per_sec)).with_sort_key(info.write_bytes_per_sec),
+        ]);
+    }
+    table.draw(stdout)?;
+    
+    Ok(())
+}
+
+// Draw the battery and power supply view
+pub fn draw_power_view<W: Write>(
+    stdout: &mut W,
+    power: &PowerStatus,
+) -> io::Result<()> {
+    // Get terminal dimensions to properly size content
+    let (term_width, term_height) = match crossterm::terminal::size() {
+        Ok((w, h)) => (w as usize, h as usize),
+        Err(_) => (80, 24), // Fallback to a reasonable default
+    };
+    
+    // Calculate content box dimensions
+    let content_width = term_width.saturating_sub(4);
+    let bar_width = content_width.saturating_sub(25); // Allow space for labels and values
+    
+    // Create a content area with a border
+    draw_content_box(stdout, "Battery & Power", 2, term_height as u16 - 3)?;
+    
+    // Start content 1 row below the header, 2 columns in from the left
+    let content_start_x = 2;
+    let content_start_y = 3;
+    let mut current_row = content_start_y;
+    let last_row = term_height as u16 - 4;
+    
+    if power.batteries.is_empty() && power.adapters.is_empty() {
+        execute!(
+            stdout,
+            MoveTo(content_start_x, current_row),
+            Print("No batteries or power adapters detected."),
+            MoveTo(content_start_x, current_row + 1),
+            Print("Power supplies are read from /sys/class/power_supply.")
+        )?;
+        return Ok(());
+    }
+    
+    // AC adapters first, since they decide whether the battery drains
+    for adapter in power.adapters.iter() {
+        let (state, color) = if adapter.online {
+            ("online", Color::Green)
+        } else {
+            ("offline", Color::DarkGrey)
+        };
+        execute!(
+            stdout,
+            MoveTo(content_start_x, current_row),
+            Print(format!("{:20} ", format!("Adapter {}:", adapter.name))),
+            SetForegroundColor(color),
+            Print(state),
+            ResetColor
+        )?;
+        current_row += 1;
+    }
+    if !power.adapters.is_empty() {
+        current_row += 1;
+    }
+    
+    for battery in power.batteries.iter() {
+        if current_row > last_row {
+            break;
+        }
+        
+        let title = if battery.model.is_empty() {
+            battery.name.clone()
+        } else {
+            format!("{} ({})", battery.name, battery.model)
+        };
+        execute!(
+            stdout,
+            MoveTo(content_start_x, current_row),
+            SetForegroundColor(Color::Green),
+            Print(format!("=== {} ===", title)),
+            ResetColor
+        )?;
+        current_row += 1;
+        
+        // Charge is colored by how little is left rather than how much is used
+        let status_color = match battery.status {
+            BatteryStatus::Charging | BatteryStatus::Full => Color::Green,
+            BatteryStatus::Discharging => thresholds().battery_charge.color(battery.capacity),
+            _ => Color::DarkGrey,
+        };
+        execute!(
+            stdout,
+            MoveTo(content_start_x, current_row),
+            Print(format!("{:20} ", "Charge:")),
+            SetForegroundColor(thresholds().battery_charge.color(battery.capacity)),
+            Print(format!("{:.0}%", battery.capacity)),
+            ResetColor,
+            Print("  "),
+            SetForegroundColor(status_color),
+            Print(battery.status.name()),
+            ResetColor
+        )?;
+        current_row += 1;
+        
+        let remaining_label = match battery.status {
+            BatteryStatus::Charging => "Time to Full:",
+            _ => "Time Remaining:",
+        };
+        let rows = [
+            ("Power Draw:", battery.power_draw.map(|watts| format!("{:.1} W", watts))),
+            (remaining_label, battery.time_remaining_secs().map(format_duration)),
+            ("Energy:", match (battery.energy_now, battery.energy_full) {
+                (Some(now), Some(full)) => Some(format!("{:.1} / {:.1} Wh", now, full)),
+                _ => None,
+            }),
+            ("Design Capacity:", battery.energy_full_design.map(|design| format!("{:.1} Wh", design))),
+            ("Cycle Count:", battery.cycle_count.map(|count| count.to_string())),
+            ("Technology:", Some(battery.technology.clone()).filter(|technology| !technology.is_empty())),
+        ];
+        for (label, value) in rows.iter() {
+            if let Some(value) = value {
+                execute!(
+                    stdout,
+                    MoveTo(content_start_x, current_row),
+                    Print(format!("{:20} {}", label, value)),
+                )?;
+                current_row += 1;
+            }
+        }
+        
+        // Wear is the share of the design capacity the battery has lost
+        if let Some(health) = battery.health_percent() {
+            current_row += 1;
+            let wear = (100.0 - health).clamp(0.0, 100.0);
+            execute!(stdout, MoveTo(content_start_x, current_row))?;
+            let wear_chart = BarChart::new("Battery Wear", wear, bar_width)
+                .with_value_label(format!("{:.1}% (health {:.0}%)", wear, health))
+                .with_thresholds(thresholds().battery_wear);
+            wear_chart.draw(stdout)?;
+            current_row += 1;
+        }
+        
+        current_row += 1;
+    }
+    
+    Ok(())
+}
+
+// Draw per-NUMA-node memory, allocation counters and hugepage pools
+pub fn draw_numa_view<W: Write>(
+    stdout: &mut W,
+    nodes: &[NumaNode],
+    hugepages: &[HugePagePool],
+) -> io::Result<()> {
+    // Get terminal dimensions to properly size content
+    let (term_width, term_height) = match crossterm::terminal::size() {
+        Ok((w, h)) => (w as usize, h as usize),
+        Err(_) => (80, 24), // Fallback to a reasonable default
+    };
+    
+    // Calculate content box dimensions
+    let content_width = term_width.saturating_sub(4);
+    let bar_width = content_width.saturating_sub(40); // Allow space for labels and sizes
+    
+    // Create a content area with a border
+    draw_content_box(stdout, "NUMA Nodes", 2, term_height as u16 - 3)?;
+    
+    // Start content 1 row below the header, 2 columns in from the left
+    let content_start_x = 2;
+    let content_start_y = 3;
+    let mut current_row = content_start_y;
+    let last_row = term_height as u16 - 4;
+    
+    if nodes.is_empty() {
+        execute!(
+            stdout,
+            MoveTo(content_start_x, current_row),
+            Print("No NUMA nodes detected."),
+            MoveTo(content_start_x, current_row + 1),
+            Print("Nodes are read from /sys/devices/system/node (Linux).")
+        )?;
+        return Ok(());
+    }
+    
+    for node in nodes {
+        if current_row + 2 > last_row {
+            break;
+        }
+        
+        execute!(
+            stdout,
+            MoveTo(content_start_x, current_row),
+            SetForegroundColor(Color::Green),
+            Print(format!("=== Node {} ===", node.id)),
+            ResetColor,
+            SetForegroundColor(Color::DarkGrey),
+            Print(format!("  CPUs {} ({})", node.cpulist, node.cpus.len())),
+            ResetColor
+        )?;
+        current_row += 1;
+        
+        execute!(stdout, MoveTo(content_start_x, current_row))?;
+        let mem_chart = BarChart::new("Memory", node.mem_used_percent(), bar_width)
+            .with_value_label(format!("{} / {} MB", node.mem_used(), node.mem_total))
+            .with_thresholds(thresholds().memory);
+        mem_chart.draw(stdout)?;
+        current_row += 1;
+        
+        // Any off-node allocation rate is worth noticing on a database host
+        let hit = node.hit_percent()
+            .map(|pct| format!("{:.2}%", pct))
+            .unwrap_or_else(|| String::from("n/a"));
+        execute!(
+            stdout,
+            MoveTo(content_start_x, current_row),
+            Print(format!(
+                "hit {}  miss {}  foreign {}  local {}  other {}  ",
+                node.stat.numa_hit,
+                node.stat.numa_miss,
+                node.stat.numa_foreign,
+                node.stat.local_node,
+                node.stat.other_node
+            )),
+            SetForegroundColor(if node.miss_per_sec > 0.0 { Color::Yellow } else { Color::DarkGrey }),
+            Print(format!("({} hit, {:.0} off-node/s)", hit, node.miss_per_sec)),
+            ResetColor
+        )?;
+        current_row += 1;
+        
+        let pools: Vec<String> = node.hugepages
+            .iter()
+            .filter(|pool| pool.total > 0)
+            .map(|pool| format!("{}: {} / {} free", pool.size_name(), pool.free, pool.total))
+            .collect();
+        if !pools.is_empty() {
+            execute!(
+                stdout,
+                MoveTo(content_start_x, current_row),
+                Print(format!("hugepages {}", pools.join("  "))),
+            )?;
+            current_row += 1;
+        }
+        
+        current_row += 1;
+    }
+    
+    // System-wide pools carry the reservation counts the per-node files lack
+    if !hugepages.is_empty() && current_row + 2 <= last_row {
+        execute!(
+            stdout,
+            MoveTo(content_start_x, current_row),
+            SetForegroundColor(Color::Green),
+            Print("=== Hugepage Pools ==="),
+            ResetColor
+        )?;
+        current_row += 1;
+        
+        let mut table = Table::new(
+            content_start_x,
+            current_row,
+            content_width,
+            last_row.saturating_sub(current_row) as usize + 1,
+            vec![
+                TableColumn::new("SIZE").min_width(6),
+                TableColumn::new("TOTAL").align(Align::Right).min_width(8),
+                TableColumn::new("FREE").align(Align::Right).min_width(8),
+                TableColumn::new("RESERVED").align(Align::Right),
+                TableColumn::new("SURPLUS").align(Align::Right),
+                TableColumn::new("MEMORY").align(Align::Right).min_width(10),
+            ],
+        );
+        for pool in hugepages {
+            let reserved = pool.reserved
+                .map(|pages| pages.to_string())
+                .unwrap_or_else(|| String::from("-"));
+            table = table.row(vec![
+                TableCell::from(pool.size_name()),
+                TableCell::from(pool.total.to_string()),
+                TableCell::from(pool.free.to_string()),
+                TableCell::from(reserved),
+                TableCell::from(pool.surplus.to_string()),
+                TableCell::from(format!("{} MB", pool.total_mb())),
+            ]);
+        }
+        table.draw(stdout)?;
+    }
+    
+    Ok(())
+}
+
+// Draw view for when no GPU is available
+pub fn draw_no_gpu_view<W: Write>(stdout: &mut W) -> io::Result<()> {
+    // Get terminal dimensions to properly size content
+    let (_, term_height) = match crossterm::terminal::size() {
+        Ok((w, h)) => (w as usize, h as usize),
+        Err(_) => (80, 24), // Fallback to a reasonable default
+    };
+    
+    // Create a content area with a border
+    draw_content_box(stdout, "GPU Details", 2, term_height as u16 - 3)?;
     
     // Start content 1 row below the header, 2 columns in from the left
     let content_start_x = 2;
@@ -645,12 +1976,223 @@ pub fn draw_no_gpu_view<W: Write>(stdout: &mut W) -> io::Result<()> {
         MoveTo(content_start_x, content_start_y + 3),
         Print("  - NVIDIA GPUs with appropriate drivers installed"),
         MoveTo(content_start_x, content_start_y + 4),
+        Print("  - AMD GPUs using the amdgpu driver (Linux)"),
+        MoveTo(content_start_x, content_start_y + 5),
+        Print("  - Intel integrated and Arc GPUs using the i915 or xe driver (Linux)"),
+        MoveTo(content_start_x, content_start_y + 6),
         Print("  - Apple Silicon M-series and Intel Macs with Metal support")
     )?;
     
     Ok(())
 }
 
+/// Series on the comparison chart: overall CPU, memory and each GPU's
+/// utilization, using the cgroup's metrics in cgroup perspective
+pub fn comparison_series(gpu_count: usize, (cpu_metric, mem_metric): (Metric, Metric)) -> Vec<(String, Metric)> {
+    let prefix = if cpu_metric == Metric::CgroupCpu { "cgroup " } else { "" };
+    let mut series = vec![
+        (format!("{}CPU", prefix), cpu_metric),
+        (format!("{}Memory", prefix), mem_metric),
+    ];
+    for i in 0..gpu_count {
+        series.push((format!("GPU #{}", i), Metric::GpuUtilization(i)));
+    }
+    series
+}
+
+// Draw CPU, memory and GPU utilization on one chart, to see which resource
+// a workload is waiting on in each phase
+pub fn draw_compare_view<W: Write>(
+    stdout: &mut W,
+    history: &HistoryStore,
+    series: &[(String, Metric)],
+    selected: usize,
+    hidden: &HashSet<usize>,
+    window: Duration,
+) -> io::Result<()> {
+    // Get terminal dimensions to properly size content
+    let (term_width, term_height) = match crossterm::terminal::size() {
+        Ok((w, h)) => (w as usize, h as usize),
+        Err(_) => (80, 24), // Fallback to a reasonable default
+    };
+    let content_width = term_width.saturating_sub(4);
+    
+    // Create a content area with a border
+    draw_content_box(stdout, "Metric Comparison", 2, term_height as u16 - 3)?;
+    
+    // Start content 1 row below the header, 2 columns in from the left
+    let content_start_x = 2;
+    let content_start_y = 3;
+    let mut current_row = content_start_y;
+    
+    // One row per series with its recent figures; the selected one is toggled with Space
+    let mut table = Table::new(
+        content_start_x,
+        current_row,
+        content_width,
+        series.len() + 1,
+        vec![
+            TableColumn::new("SHOW"),
+            TableColumn::new("SERIES").min_width(14),
+            TableColumn::new("NOW").align(Align::Right).min_width(6),
+            TableColumn::new(&format!("AVG {}", format_window(window))).align(Align::Right),
+            TableColumn::new(&format!("PEAK {}", format_window(window))).align(Align::Right),
+        ],
+    )
+    .with_selected(selected.min(series.len().saturating_sub(1)));
+    
+    for (i, (label, metric)) in series.iter().enumerate() {
+        let rollups = history.rollups(*metric, window);
+        let figure = |value: Option<f32>| match value {
+            Some(value) => TableCell::colored(format!("{:.1}%", value), usage_color(value)),
+            None => TableCell::from("-"),
+        };
+        let count: u32 = rollups.iter().map(|rollup| rollup.count).sum();
+        let average = if count > 0 {
+            Some(rollups.iter().map(|rollup| rollup.average() * rollup.count as f32).sum::<f32>() / count as f32)
+        } else {
+            None
+        };
+        let peak = rollups.iter().map(|rollup| rollup.max).reduce(f32::max);
+        let now = history.recent(*metric, Duration::from_secs(60)).last().copied();
+        
+        table = table.row(vec![
+            TableCell::from(if hidden.contains(&i) { "[ ]" } else { "[x]" }),
+            TableCell::colored(format!("■ {}", label), SEGMENT_COLORS[i % SEGMENT_COLORS.len()]),
+            figure(now),
+            figure(average),
+            figure(peak),
+        ]);
+    }
+    table.draw(stdout)?;
+    current_row += table.height() as u16 + 1;
+    
+    // Everything on a fixed 0-100% axis so the lines are directly comparable
+    let box_bottom = term_height as u16 - 3;
+    if box_bottom.saturating_sub(current_row) >= 6 {
+        let mut chart = LineChart::within_content_box(current_row, box_bottom, window)
+            .with_unit("%")
+            .with_y_range(0.0, 100.0);
+        for (i, (label, metric)) in series.iter().enumerate() {
+            if hidden.contains(&i) {
+                continue;
+            }
+            
+            chart = chart.with_series(ChartSeries {
+                label: label.clone(),
+                color: SEGMENT_COLORS[i % SEGMENT_COLORS.len()],
+                points: chart_points(history, *metric, window),
+            });
+        }
+        chart.draw(stdout)?;
+    }
+    
+    Ok(())
+}
+
+/// Metrics whose distribution can be shown from a view: the ones the view
+/// is about, or the overall CPU, memory and GPU utilization for views without
+/// recorded history
+pub fn histogram_metrics(
+    view: ViewType,
+    core_count: usize,
+    gpu_count: usize,
+    (cpu_metric, mem_metric): (Metric, Metric),
+) -> Vec<Metric> {
+    match view {
+        ViewType::CpuDetailed => {
+            std::iter::once(cpu_metric).chain((0..core_count).map(Metric::CpuCore)).collect()
+        },
+        ViewType::MemoryDetailed => vec![mem_metric, Metric::SwapUsed],
+        ViewType::GpuDetailed => (0..gpu_count)
+            .flat_map(|i| {
+                [
+                    Metric::GpuUtilization(i),
+                    Metric::GpuMemory(i),
+                    Metric::GpuTemperature(i),
+                    Metric::GpuPower(i),
+                ]
+            })
+            .collect(),
+        ViewType::Pressure => PsiResource::ALL.iter().map(|resource| Metric::Pressure(*resource)).collect(),
+        _ => comparison_series(gpu_count, (cpu_metric, mem_metric))
+            .into_iter()
+            .map(|(_, metric)| metric)
+            .collect(),
+    }
+}
+
+// Draw how one metric's values were distributed over the histogram window,
+// in place of the current view
+pub fn draw_histogram_view<W: Write>(
+    stdout: &mut W,
+    history: &HistoryStore,
+    metrics: &[Metric],
+    selected: usize,
+) -> io::Result<()> {
+    // Get terminal dimensions to properly size content
+    let (term_width, term_height) = match crossterm::terminal::size() {
+        Ok((w, h)) => (w as usize, h as usize),
+        Err(_) => (80, 24), // Fallback to a reasonable default
+    };
+    let content_width = term_width.saturating_sub(4);
+    
+    // Create a content area with a border
+    draw_content_box(stdout, "Distribution", 2, term_height as u16 - 3)?;
+    
+    // Start content 1 row below the header, 2 columns in from the left
+    let content_start_x = 2;
+    let content_start_y = 3;
+    let mut current_row = content_start_y;
+    let last_row = term_height as u16 - 4;
+    
+    let metric = match metrics.get(selected.min(metrics.len().saturating_sub(1))) {
+        Some(metric) => *metric,
+        None => {
+            execute!(stdout, MoveTo(content_start_x, current_row), Print("No metrics to show."))?;
+            return Ok(());
+        },
+    };
+    let values = history.recent(metric, HISTOGRAM_WINDOW);
+    
+    execute!(
+        stdout,
+        MoveTo(content_start_x, current_row),
+        SetForegroundColor(Color::Green),
+        Print(format!("=== {} ===", metric.name())),
+        ResetColor,
+        SetForegroundColor(Color::DarkGrey),
+        Print(format!(
+            "  last {} min, {} samples  (Up/Down: metric {}/{}, d: close)",
+            HISTOGRAM_WINDOW.as_secs() / 60,
+            values.len(),
+            selected.min(metrics.len() - 1) + 1,
+            metrics.len()
+        )),
+        ResetColor
+    )?;
+    current_row += 2;
+    
+    if current_row > last_row {
+        return Ok(());
+    }
+    
+    let mut histogram = Histogram::new(
+        content_start_x,
+        current_row,
+        content_width,
+        last_row.saturating_sub(current_row) as usize + 1,
+        &values,
+    )
+    .with_unit(metric.unit());
+    if metric.unit() == "%" {
+        histogram = histogram.with_range(0.0, 100.0);
+    }
+    histogram.draw(stdout)?;
+    
+    Ok(())
+}
+
 // Draw help view with keyboard shortcuts
 pub fn draw_help_view<W: Write>(stdout: &mut W) -> io::Result<()> {
     // Get terminal dimensions to properly size content
@@ -658,6 +2200,7 @@ pub fn draw_help_view<W: Write>(stdout: &mut W) -> io::Result<()> {
         Ok((w, h)) => (w as usize, h as usize),
         Err(_) => (80, 24), // Fallback to a reasonable default
     };
+    let content_width = term_width.saturating_sub(4);
     
     // Create a content area with a border
     draw_content_box(stdout, "Keyboard Controls", 2, term_height as u16 - 3)?;
@@ -665,65 +2208,104 @@ pub fn draw_help_view<W: Write>(stdout: &mut W) -> io::Result<()> {
     // Start content 1 row below the header, 2 columns in from the left
     let content_start_x = 4; // Indent a bit more for better readability
     let content_start_y = 3;
-    let mut current_row = content_start_y;
-    
-    let help_items = [
-        ("Navigation", ""),
-        ("  Tab", "Next view"),
-        ("  Shift+Tab", "Previous view"),
-        ("  1", "Overview"),
-        ("  2", "CPU details"),
-        ("  3", "Memory details"),
-        ("  4", "GPU details (if available)"),
-        ("  ? or h", "Show this help"),
-        ("", ""),
-        ("Controls", ""),
-        ("  p", "Pause/resume automatic updates"),
-        ("  r", "Force refresh now"),
-        ("", ""),
-        ("Exit", ""),
-        ("  q or Esc", "Quit"),
-        ("  Ctrl+c", "Quit"),
+    let last_row = term_height as u16 - 4;
+    
+    let help_sections: [(&str, &[(&str, &str)]); 3] = [
+        ("Navigation", &[
+            ("Tab", "Next view"),
+            ("Shift+Tab", "Previous view"),
+            ("1", "Overview"),
+            ("2", "CPU details"),
+            ("3", "Memory details"),
+            ("4", "GPU details (if any)"),
+            ("5", "Disk I/O"),
+            ("6", "Temperature sensors"),
+            ("7", "Pressure stall info"),
+            ("8", "Containers"),
+            ("9", "Battery and power"),
+            ("0", "NUMA and hugepages"),
+            ("m", "Metric comparison"),
+            ("? or h", "Show this help"),
+        ]),
+        ("Controls", &[
+            ("p", "Pause/resume updates"),
+            ("r", "Force refresh now"),
+            ("g", "Toggle cgroup view"),
+            ("c", "Heatmap core history"),
+            ("d", "Metric distribution"),
+            ("+/-", "Zoom charts 1m to 24h"),
+            ("Up/Down", "Select a table row"),
+            ("Space", "Show/hide a series"),
+            ("s / S", "Sort column / reverse"),
+        ]),
+        ("Exit", &[
+            ("q or Esc", "Quit"),
+            ("Ctrl+c", "Quit"),
+        ]),
     ];
     
-    for (key, description) in help_items.iter() {
-        if key.is_empty() {
-            current_row += 1; // Add empty row for spacing
-        } else if !description.is_empty() {
+    // Sections continue in a second column when one would run past the box
+    let rows_needed: usize = help_sections.iter().map(|(_, items)| items.len() + 2).sum();
+    let two_columns = rows_needed > (last_row - content_start_y) as usize + 2;
+    let column_width = if two_columns { content_width / 2 } else { content_width };
+    let description_width = column_width.saturating_sub(16);
+    
+    let mut x = content_start_x;
+    let mut current_row = content_start_y;
+    let mut lowest_row = current_row;
+    
+    for (section, items) in help_sections.iter() {
+        let section_bottom = current_row + items.len() as u16;
+        if two_columns && x == content_start_x && current_row > content_start_y && section_bottom > last_row {
+            x = content_start_x + column_width as u16;
+            current_row = content_start_y;
+        }
+        
+        // Section header
+        execute!(
+            stdout,
+            MoveTo(x - 2, current_row),
+            SetForegroundColor(Color::Green),
+            Print(format!("» {}", section)),
+            ResetColor
+        )?;
+        current_row += 1;
+        
+        for (key, description) in items.iter() {
+            if current_row > last_row {
+                break;
+            }
+            
+            let description: String = description.chars().take(description_width).collect();
             execute!(
                 stdout,
-                MoveTo(content_start_x, current_row),
+                MoveTo(x, current_row),
                 SetForegroundColor(Color::Yellow),
-                Print(format!("{:12}", key)),
+                Print(format!("  {:10}", key)),
                 ResetColor,
                 Print(format!(" → {}", description))
             )?;
             current_row += 1;
-        } else {
-            // Section header
-            execute!(
-                stdout,
-                MoveTo(content_start_x - 2, current_row),
-                SetForegroundColor(Color::Green),
-                Print(format!("» {}", key)),
-                ResetColor
-            )?;
-            current_row += 1;
         }
+        
+        lowest_row = lowest_row.max(current_row);
+        current_row += 1; // Add empty row for spacing
     }
     
-    // Add a note about the application at the bottom
-    execute!(
-        stdout,
-        MoveTo(content_start_x, term_height as u16 - 5),
-        SetForegroundColor(Color::DarkGrey),
-        Print("ezstats is a lightweight terminal-based system monitor"),
-        MoveTo(content_start_x, term_height as u16 - 4),
-        Print("designed for minimal resource usage while providing"),
-        MoveTo(content_start_x, term_height as u16 - 3),
-        Print("real-time monitoring of system resources."),
-        ResetColor
-    )?;
+    // Add a note about the application at the bottom when there is room for it
+    if lowest_row + 3 < last_row {
+        execute!(
+            stdout,
+            MoveTo(content_start_x, last_row - 2),
+            SetForegroundColor(Color::DarkGrey),
+            Print("ezstats is a lightweight terminal-based system monitor"),
+            MoveTo(content_start_x, last_row - 1),
+            Print("designed for minimal resource usage while providing"),
+            MoveTo(content_start_x, last_row),
+            Print("real-time monitoring of system resources."),
+            ResetColor
+        )?;
+    }
     
     Ok(())
 }
\ No newline at end of file
@@ -2,50 +2,175 @@
 // Improved widgets with better error handling and rendering
 
 use std::io::{self, Write};
+use std::time::Duration;
 use crossterm::{
-    style::{Color, SetForegroundColor, ResetColor},
+    cursor::MoveTo,
+    style::{Attribute, Color, SetAttribute, SetForegroundColor, ResetColor},
     execute,
 };
 
+use crate::config::thresholds;
+
 /// Represents a simple widget that can be drawn in the terminal
 pub trait Widget {
     fn draw(&self, stdout: &mut impl Write) -> io::Result<()>;
 }
 
+/// The values at which a metric turns yellow and then red
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Thresholds {
+    pub warning: f32,
+    pub critical: f32,
+    pub inverted: bool,  // Low values are bad, e.g. free memory or battery charge
+}
+
+impl Thresholds {
+    /// General utilization: yellow above 50%, red above 80%
+    pub const USAGE: Thresholds = Thresholds::new(50.0, 80.0);
+    
+    /// Thresholds for a metric that is worse the higher it gets
+    pub const fn new(warning: f32, critical: f32) -> Self {
+        Thresholds { warning, critical, inverted: false }
+    }
+    
+    /// Thresholds for a metric that is worse the lower it gets, turning
+    /// yellow below `warning` and red below `critical`
+    pub const fn inverted(warning: f32, critical: f32) -> Self {
+        Thresholds { warning, critical, inverted: true }
+    }
+    
+    /// Get the green/yellow/red color for a value
+    pub fn color(&self, value: f32) -> Color {
+        let (value, warning, critical) = self.oriented(value);
+        if value > critical {
+            Color::Red
+        } else if value > warning {
+            Color::Yellow
+        } else {
+            Color::Green
+        }
+    }
+    
+    /// Get a color blended from green through yellow to red for a percentage,
+    /// reaching yellow at the warning threshold and red at the critical one
+    pub fn gradient_color(&self, value: f32) -> Color {
+        // Distance from the good end of the scale: 0 = best, 1 = warning, 2 = critical
+        let (value, warning, critical) = self.oriented(value.clamp(0.0, 100.0));
+        let best = if self.inverted { -100.0 } else { 0.0 };
+        let fraction = |from: f32, to: f32| {
+            if to > from { ((value - from) / (to - from)).clamp(0.0, 1.0) } else { 1.0 }
+        };
+        let severity = if value <= warning {
+            fraction(best, warning)
+        } else {
+            1.0 + fraction(warning, critical)
+        };
+        
+        // Red rises to full by the warning threshold, then green fades out
+        let red = severity.min(1.0);
+        let green = 1.0 - (severity - 1.0).max(0.0);
+        
+        Color::Rgb {
+            r: (red * 220.0) as u8,
+            g: (green * 200.0) as u8,
+            b: 0,
+        }
+    }
+    
+    // Flip an inverted scale so larger always means worse
+    fn oriented(&self, value: f32) -> (f32, f32, f32) {
+        if self.inverted {
+            (-value, -self.warning, -self.critical)
+        } else {
+            (value, self.warning, self.critical)
+        }
+    }
+}
+
+/// Get the green/yellow/red color for a usage percentage
+pub fn usage_color(value: f32) -> Color {
+    thresholds().usage.color(value)
+}
+
 /// A bar chart widget for displaying usage metrics (CPU, RAM)
 pub struct BarChart {
     title: String,
     value: f32,  // Value as a percentage (0-100)
     width: usize,
+    value_label: Option<String>,  // Replaces the percentage text when set
+    thresholds: Thresholds,
+    gradient: bool,       // Shade each filled cell by its position instead of one color
+    limit: Option<f32>,   // Percentage at which to draw a limit marker
 }
 
 impl BarChart {
     /// Create a new bar chart with the given title and value
     pub fn new(title: &str, value: f32, width: usize) -> Self {
         // Ensure width is reasonable
-        let safe_width = width.max(10).min(200);
+        let safe_width = width.clamp(10, 200);
         
         BarChart {
             title: title.to_string(),
             value: value.clamp(0.0, 100.0),
             width: safe_width,
+            value_label: None,
+            thresholds: thresholds().usage,
+            gradient: false,
+            limit: None,
         }
     }
     
+    /// Show custom text (e.g. a temperature) instead of the percentage
+    pub fn with_value_label(mut self, label: String) -> Self {
+        self.value_label = Some(label);
+        self
+    }
+    
+    /// Color the bar with the given thresholds instead of the usage defaults
+    pub fn with_thresholds(mut self, thresholds: Thresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+    
+    /// Shade the filled portion from green to red along its length
+    pub fn gradient(mut self) -> Self {
+        self.gradient = true;
+        self
+    }
+    
+    /// Mark a limit (e.g. a cgroup's memory.max) on the bar, as a percentage
+    pub fn with_limit(mut self, limit: f32) -> Self {
+        self.limit = Some(limit.clamp(0.0, 100.0));
+        self
+    }
+    
     /// Get the appropriate color based on the value
     fn get_color(&self) -> Color {
-        if self.value > 80.0 {
-            Color::Red
-        } else if self.value > 50.0 {
-            Color::Yellow
+        self.thresholds.color(self.value)
+    }
+    
+    // Color of one bar cell: the value's color, or its position's in gradient mode
+    fn cell_color(&self, column: usize) -> Color {
+        if self.gradient {
+            let position = (column as f32 + 0.5) / self.width as f32 * 100.0;
+            self.thresholds.gradient_color(position)
         } else {
-            Color::Green
+            self.get_color()
         }
     }
     
+    // Column of the limit marker, if one is set
+    fn limit_column(&self) -> Option<usize> {
+        self.limit
+            .map(|limit| ((limit / 100.0 * self.width as f32) as usize).min(self.width - 1))
+    }
+    
     /// Get a textual representation of the value for display
     fn format_value(&self) -> String {
-        format!("{:.1}%", self.value)
+        match &self.value_label {
+            Some(label) => label.clone(),
+            None => format!("{:.1}%", self.value),
+        }
     }
 }
 
@@ -69,8 +194,19 @@ impl Widget for BarChart {
             ResetColor
         )?;
         
-        // Draw the filled portion (if any)
-        if filled_width > 0 {
+        // Draw the filled portion (if any), cell by cell when each may differ
+        let limit_column = self.limit_column();
+        if self.gradient || limit_column.map(|column| column < filled_width).unwrap_or(false) {
+            for column in 0..filled_width {
+                let (color, symbol) = if limit_column == Some(column) {
+                    (Color::White, "│")
+                } else {
+                    (self.cell_color(column), "█")
+                };
+                execute!(stdout, SetForegroundColor(color), crossterm::style::Print(symbol))?;
+            }
+            execute!(stdout, ResetColor)?;
+        } else if filled_width > 0 {
             execute!(
                 stdout,
                 SetForegroundColor(self.get_color()),
@@ -79,14 +215,28 @@ impl Widget for BarChart {
             )?;
         }
         
-        // Draw the empty portion (if any)
+        // Draw the empty portion (if any), broken by the limit marker when it falls there
         if empty_width > 0 {
+            let (before, after) = match limit_column {
+                Some(column) if column >= filled_width => (column - filled_width, empty_width - (column - filled_width) - 1),
+                _ => (empty_width, 0),
+            };
             execute!(
                 stdout,
                 SetForegroundColor(Color::DarkGrey),
-                crossterm::style::Print("░".repeat(empty_width)),
+                crossterm::style::Print("░".repeat(before)),
                 ResetColor
             )?;
+            if before < empty_width {
+                execute!(
+                    stdout,
+                    SetForegroundColor(Color::White),
+                    crossterm::style::Print("│"),
+                    SetForegroundColor(Color::DarkGrey),
+                    crossterm::style::Print("░".repeat(after)),
+                    ResetColor
+                )?;
+            }
         }
         
         // Fixed value width for consistent display
@@ -105,12 +255,1208 @@ impl Widget for BarChart {
     }
 }
 
+/// Colors for segments that have no fixed meaning (e.g. one per process),
+/// assigned in order so the same position always gets the same color
+pub const SEGMENT_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Magenta,
+    Color::Yellow,
+    Color::Blue,
+    Color::Green,
+    Color::Red,
+];
+
+/// A labeled part of a stacked bar
+#[derive(Clone, Debug)]
+pub struct BarSegment {
+    pub label: String,  // Shown in the legend, e.g. "Cache 1373 MB"
+    pub value: f32,
+    pub color: Color,
+}
+
+impl BarSegment {
+    pub fn new(label: String, value: f32, color: Color) -> Self {
+        BarSegment { label, value, color }
+    }
+}
+
+/// A bar split into colored segments proportional to their share of a total,
+/// for quantities made of parts (memory used/cache/free, CPU time by state)
+pub struct StackedBar {
+    x: u16,
+    y: u16,
+    title: String,
+    segments: Vec<BarSegment>,
+    total: f32,         // Anything the segments don't account for is drawn empty
+    width: usize,
+    value_label: Option<String>,
+    legend_width: Option<usize>,  // Columns from `x` the legend below the bar may use
+}
+
+impl StackedBar {
+    /// Create a stacked bar at the given position, scaled so `total` fills `width`
+    pub fn new(x: u16, y: u16, title: &str, segments: Vec<BarSegment>, total: f32, width: usize) -> Self {
+        StackedBar {
+            x,
+            y,
+            title: title.to_string(),
+            segments,
+            total,
+            width: width.clamp(10, 200),
+            value_label: None,
+            legend_width: None,
+        }
+    }
+    
+    /// Show text (e.g. the busy percentage) to the right of the bar
+    pub fn with_value_label(mut self, label: String) -> Self {
+        self.value_label = Some(label);
+        self
+    }
+    
+    /// Draw a legend of the segments on the row below the bar, fitted within
+    /// `width` columns of the bar's left edge
+    pub fn with_legend(mut self, width: usize) -> Self {
+        self.legend_width = Some(width);
+        self
+    }
+    
+    /// Number of terminal rows the bar occupies when drawn
+    pub fn height(&self) -> u16 {
+        if self.legend_width.is_some() { 2 } else { 1 }
+    }
+    
+    // Width of each segment, rounding cumulative boundaries so the segments
+    // never drift past the bar
+    fn segment_widths(&self) -> Vec<usize> {
+        let total = if self.total > 0.0 { self.total } else { 1.0 };
+        let mut cumulative = 0.0;
+        let mut drawn = 0;
+        
+        self.segments
+            .iter()
+            .map(|segment| {
+                cumulative += segment.value.max(0.0);
+                let end = (((cumulative / total) * self.width as f32).round() as usize).min(self.width);
+                let width = end.saturating_sub(drawn);
+                drawn += width;
+                width
+            })
+            .collect()
+    }
+    
+    // Draw the legend, leaving out entries that would not fit in `width`
+    fn draw_legend(&self, stdout: &mut impl Write, width: usize) -> io::Result<()> {
+        execute!(stdout, MoveTo(self.x + 15, self.y + 1))?;
+        
+        let available = width.saturating_sub(15);
+        let mut used = 0;
+        for (i, segment) in self.segments.iter().enumerate() {
+            // The trailing gap can run past the edge
+            let entry_width = segment.label.chars().count() + 2;
+            
+            // Keep room to say how many entries were left out
+            let remaining = self.segments.len() - i - 1;
+            let reserve = if remaining > 0 { 8 } else { 0 };
+            if used + entry_width + reserve > available {
+                execute!(
+                    stdout,
+                    SetForegroundColor(Color::DarkGrey),
+                    crossterm::style::Print(format!("+{} more", self.segments.len() - i)),
+                    ResetColor
+                )?;
+                break;
+            }
+            
+            execute!(
+                stdout,
+                SetForegroundColor(segment.color),
+                crossterm::style::Print("■ "),
+                ResetColor,
+                crossterm::style::Print(format!("{}  ", segment.label))
+            )?;
+            used += entry_width + 2;
+        }
+        
+        Ok(())
+    }
+}
+
+impl Widget for StackedBar {
+    fn draw(&self, stdout: &mut impl Write) -> io::Result<()> {
+        execute!(
+            stdout,
+            MoveTo(self.x, self.y),
+            SetForegroundColor(Color::White),
+            crossterm::style::Print(format!("{:<15}", self.title)),
+            ResetColor
+        )?;
+        
+        let widths = self.segment_widths();
+        for (segment, width) in self.segments.iter().zip(widths.iter()) {
+            execute!(
+                stdout,
+                SetForegroundColor(segment.color),
+                crossterm::style::Print("█".repeat(*width)),
+                ResetColor
+            )?;
+        }
+        
+        let drawn: usize = widths.iter().sum();
+        execute!(
+            stdout,
+            SetForegroundColor(Color::DarkGrey),
+            crossterm::style::Print("░".repeat(self.width.saturating_sub(drawn))),
+            ResetColor
+        )?;
+        
+        if let Some(label) = &self.value_label {
+            execute!(stdout, crossterm::style::Print(format!(" {}", label)))?;
+        }
+        
+        if let Some(width) = self.legend_width {
+            self.draw_legend(stdout, width)?;
+        }
+        
+        Ok(())
+    }
+}
+
+/// A one-row chart of recent values drawn with the eight block heights
+pub struct Sparkline {
+    values: Vec<f32>,
+    width: usize,
+    max: Option<f32>,  // Top of the scale; the series peak when unset
+    thresholds: Thresholds,
+}
+
+impl Sparkline {
+    /// Create a sparkline of `values` (oldest first) fitted to `width` columns
+    pub fn new(values: &[f32], width: usize) -> Self {
+        Sparkline {
+            values: values.to_vec(),
+            width: width.max(1),
+            max: None,
+            thresholds: thresholds().usage,
+        }
+    }
+    
+    /// Scale against a fixed maximum (e.g. 100 for percentages) instead of the peak
+    pub fn with_max(mut self, max: f32) -> Self {
+        self.max = Some(max);
+        self
+    }
+    
+    /// Color values with the given thresholds, matching the bar next to the sparkline
+    pub fn with_thresholds(mut self, thresholds: Thresholds) -> Self {
+        self.thresholds = thresholds;
+        self
+    }
+    
+    /// Fit the series to the width, averaging neighbours when there are more
+    /// values than columns
+    fn fitted_values(&self) -> Vec<f32> {
+        if self.values.len() <= self.width {
+            return self.values.clone();
+        }
+        
+        (0..self.width)
+            .map(|column| {
+                let start = column * self.values.len() / self.width;
+                let end = ((column + 1) * self.values.len() / self.width).max(start + 1);
+                let bucket = &self.values[start..end];
+                bucket.iter().sum::<f32>() / bucket.len() as f32
+            })
+            .collect()
+    }
+}
+
+impl Widget for Sparkline {
+    fn draw(&self, stdout: &mut impl Write) -> io::Result<()> {
+        const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+        
+        let values = self.fitted_values();
+        let peak = self.max.unwrap_or_else(|| values.iter().cloned().fold(1.0_f32, f32::max));
+        
+        // Right-align so the newest value is always in the last column
+        execute!(
+            stdout,
+            crossterm::style::Print(" ".repeat(self.width.saturating_sub(values.len())))
+        )?;
+        
+        for value in values {
+            let level = ((value / peak).clamp(0.0, 1.0) * (BLOCKS.len() - 1) as f32).round() as usize;
+            execute!(
+                stdout,
+                SetForegroundColor(self.thresholds.color(value)),
+                crossterm::style::Print(BLOCKS[level.min(BLOCKS.len() - 1)])
+            )?;
+        }
+        
+        execute!(stdout, ResetColor)?;
+        
+        Ok(())
+    }
+}
+
+/// One line plotted by a LineChart
+pub struct ChartSeries {
+    pub label: String,
+    pub color: Color,
+    pub points: Vec<(f32, f32)>,  // (seconds before now, value), oldest first
+}
+
+/// A multi-series time chart drawn with Braille characters, which give each
+/// terminal cell a 2x4 grid of dots
+pub struct LineChart {
+    x: u16,
+    y: u16,
+    width: usize,
+    height: usize,
+    window: Duration,
+    unit: String,
+    y_range: Option<(f32, f32)>,  // Fixed Y axis; scaled to the data when unset
+    series: Vec<ChartSeries>,
+}
+
+// Bit of each dot in a Braille cell, indexed by [row][column]
+const BRAILLE_DOTS: [[u8; 2]; 4] = [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]];
+
+// Columns taken by the Y-axis labels and the axis line
+const Y_LABEL_WIDTH: usize = 7;
+
+// Format a time-axis tick as an offset from now
+fn format_tick(secs_ago: f32) -> String {
+    if secs_ago < 0.5 {
+        String::from("now")
+    } else if secs_ago < 60.0 {
+        format!("-{:.0}s", secs_ago)
+    } else if secs_ago < 3600.0 {
+        format!("-{}m", trim_decimal(secs_ago / 60.0))
+    } else {
+        format!("-{}h", trim_decimal(secs_ago / 3600.0))
+    }
+}
+
+// Show a value with one decimal only when it is small and not whole
+fn trim_decimal(value: f32) -> String {
+    if value >= 10.0 || (value - value.round()).abs() < 0.05 {
+        format!("{:.0}", value)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+impl LineChart {
+    /// Create a chart occupying the given area of the screen
+    pub fn new(x: u16, y: u16, width: usize, height: usize, window: Duration) -> Self {
+        LineChart {
+            x,
+            y,
+            width: width.max(Y_LABEL_WIDTH + 2),
+            height: height.max(4),
+            window,
+            unit: String::new(),
+            y_range: None,
+            series: Vec::new(),
+        }
+    }
+    
+    /// Create a chart filling the inside of a box drawn by `draw_content_box`,
+    /// from `first_row` down to just above the box's bottom border at `box_bottom`
+    pub fn within_content_box(first_row: u16, box_bottom: u16, window: Duration) -> Self {
+        let term_width = crossterm::terminal::size().map(|(w, _)| w).unwrap_or(80);
+        let height = box_bottom.saturating_sub(first_row) as usize;
+        Self::new(2, first_row, term_width.saturating_sub(4) as usize, height, window)
+    }
+    
+    /// Append a unit (e.g. "%") to the Y-axis labels
+    pub fn with_unit(mut self, unit: &str) -> Self {
+        self.unit = unit.to_string();
+        self
+    }
+    
+    /// Fix the Y axis (e.g. 0-100 for percentages) so series stay comparable
+    /// as they come and go
+    pub fn with_y_range(mut self, min: f32, max: f32) -> Self {
+        self.y_range = Some((min, max.max(min + 1.0)));
+        self
+    }
+    
+    /// Add a series to the chart
+    pub fn with_series(mut self, series: ChartSeries) -> Self {
+        self.series.push(series);
+        self
+    }
+    
+    // Lowest and highest value on the Y axis, from zero to just above the peak
+    // unless a range is fixed
+    fn y_bounds(&self) -> (f32, f32) {
+        if let Some(range) = self.y_range {
+            return range;
+        }
+        
+        let peak = self.series
+            .iter()
+            .flat_map(|series| series.points.iter().map(|(_, value)| *value))
+            .fold(0.0_f32, f32::max);
+        (0.0, (peak * 1.1).max(1.0))
+    }
+    
+    // Plot every series into per-cell dot masks, remembering which series
+    // last touched each cell for its color
+    fn plot(&self, plot_width: usize, plot_height: usize) -> Vec<Vec<(u8, Option<Color>)>> {
+        let mut cells = vec![vec![(0u8, None); plot_width]; plot_height];
+        let dots_wide = plot_width * 2;
+        let dots_high = plot_height * 4;
+        let window = self.window.as_secs_f32().max(f32::EPSILON);
+        let (min, max) = self.y_bounds();
+        
+        let to_dot = |(secs_ago, value): (f32, f32)| -> (i64, i64) {
+            let x = ((window - secs_ago) / window * (dots_wide - 1) as f32).round() as i64;
+            let level = ((value - min) / (max - min)).clamp(0.0, 1.0);
+            let y = (dots_high - 1) as i64 - (level * (dots_high - 1) as f32).round() as i64;
+            (x, y)
+        };
+        
+        for series in self.series.iter() {
+            let dots: Vec<(i64, i64)> = series.points
+                .iter()
+                .filter(|(secs_ago, _)| *secs_ago <= window)
+                .map(|point| to_dot(*point))
+                .collect();
+            
+            let mut set_dot = |x: i64, y: i64| {
+                if x < 0 || y < 0 || x >= dots_wide as i64 || y >= dots_high as i64 {
+                    return;
+                }
+                let cell = &mut cells[y as usize / 4][x as usize / 2];
+                cell.0 |= BRAILLE_DOTS[y as usize % 4][x as usize % 2];
+                cell.1 = Some(series.color);
+            };
+            
+            if dots.len() == 1 {
+                set_dot(dots[0].0, dots[0].1);
+            }
+            
+            // Connect neighbouring samples so gaps between refreshes stay joined
+            for pair in dots.windows(2) {
+                let (x0, y0) = pair[0];
+                let (x1, y1) = pair[1];
+                let steps = (x1 - x0).abs().max((y1 - y0).abs()).max(1);
+                for step in 0..=steps {
+                    let x = x0 + (x1 - x0) * step / steps;
+                    let y = y0 + (y1 - y0) * step / steps;
+                    set_dot(x, y);
+                }
+            }
+        }
+        
+        cells
+    }
+}
+
+impl Widget for LineChart {
+    fn draw(&self, stdout: &mut impl Write) -> io::Result<()> {
+        // Rows below the plot: axis line, tick labels and legend
+        let plot_height = self.height.saturating_sub(3).max(1);
+        let plot_width = self.width.saturating_sub(Y_LABEL_WIDTH + 1).max(1);
+        let (min, max) = self.y_bounds();
+        let cells = self.plot(plot_width, plot_height);
+        
+        for (row, line) in cells.iter().enumerate() {
+            // Label the top, middle and bottom rows of the Y axis
+            let label = if row == 0 || row == plot_height - 1 || row == plot_height / 2 {
+                let level = 1.0 - row as f32 / (plot_height - 1).max(1) as f32;
+                let value = min + (max - min) * level;
+                let value = if max - min < 10.0 { format!("{:.1}", value) } else { format!("{:.0}", value) };
+                format!("{:>width$}┤", format!("{}{}", value, self.unit), width = Y_LABEL_WIDTH - 1)
+            } else {
+                format!("{:>width$}│", "", width = Y_LABEL_WIDTH - 1)
+            };
+            
+            execute!(
+                stdout,
+                MoveTo(self.x, self.y + row as u16),
+                SetForegroundColor(Color::DarkGrey),
+                crossterm::style::Print(label),
+                ResetColor
+            )?;
+            
+            for (mask, color) in line.iter() {
+                match color {
+                    Some(color) => execute!(
+                        stdout,
+                        SetForegroundColor(*color),
+                        crossterm::style::Print(char::from_u32(0x2800 + *mask as u32).unwrap_or(' '))
+                    )?,
+                    None => execute!(stdout, crossterm::style::Print(' '))?,
+                }
+            }
+            execute!(stdout, ResetColor)?;
+        }
+        
+        // Time axis with evenly spaced ticks from the start of the window to now
+        let tick_count = (plot_width / 16).clamp(1, 6);
+        let tick_columns: Vec<usize> = (0..=tick_count)
+            .map(|tick| tick * (plot_width - 1) / tick_count)
+            .collect();
+        
+        let axis: String = (0..plot_width)
+            .map(|column| if tick_columns.contains(&column) { '┬' } else { '─' })
+            .collect();
+        let axis_row = self.y + plot_height as u16;
+        execute!(
+            stdout,
+            MoveTo(self.x, axis_row),
+            SetForegroundColor(Color::DarkGrey),
+            crossterm::style::Print(format!("{:>width$}└{}", "", axis, width = Y_LABEL_WIDTH - 1)),
+        )?;
+        
+        let window = self.window.as_secs_f32();
+        for column in tick_columns.iter() {
+            let secs_ago = window * (1.0 - *column as f32 / (plot_width - 1).max(1) as f32);
+            let label = format_tick(secs_ago);
+            
+            // Keep the last label inside the chart
+            let x = (Y_LABEL_WIDTH + column).min(Y_LABEL_WIDTH + plot_width - label.len());
+            execute!(stdout, MoveTo(self.x + x as u16, axis_row + 1), crossterm::style::Print(label))?;
+        }
+        execute!(stdout, ResetColor)?;
+        
+        // Legend
+        execute!(stdout, MoveTo(self.x + Y_LABEL_WIDTH as u16, axis_row + 2))?;
+        for series in self.series.iter() {
+            execute!(
+                stdout,
+                SetForegroundColor(series.color),
+                crossterm::style::Print("■ "),
+                ResetColor,
+                crossterm::style::Print(format!("{}  ", series.label))
+            )?;
+        }
+        
+        Ok(())
+    }
+}
+
+/// Value below which `p` percent of the samples fall, from sorted samples
+pub fn percentile(sorted: &[f32], p: f32) -> f32 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    
+    // Nearest rank, so the result is always a value that was actually seen
+    let rank = ((p / 100.0) * sorted.len() as f32).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+/// Horizontal bars counting how many samples fell into each range of values,
+/// headed by the main percentiles, to show how a metric is distributed
+pub struct Histogram {
+    x: u16,
+    y: u16,
+    width: usize,
+    height: usize,
+    values: Vec<f32>,
+    range: Option<(f32, f32)>,  // Fixed bucket range; the data's min and max when unset
+    unit: String,
+}
+
+// Most buckets a histogram is split into
+const MAX_BUCKETS: usize = 10;
+
+impl Histogram {
+    /// Create a histogram of `values` occupying the given area of the screen
+    pub fn new(x: u16, y: u16, width: usize, height: usize, values: &[f32]) -> Self {
+        Histogram {
+            x,
+            y,
+            width: width.max(30),
+            height: height.max(3),
+            values: values.to_vec(),
+            range: None,
+            unit: String::new(),
+        }
+    }
+    
+    /// Bucket over a fixed range (e.g. 0-100 for percentages)
+    pub fn with_range(mut self, min: f32, max: f32) -> Self {
+        self.range = Some((min, max));
+        self
+    }
+    
+    /// Append a unit (e.g. "%") to values and bucket labels
+    pub fn with_unit(mut self, unit: &str) -> Self {
+        self.unit = unit.to_string();
+        self
+    }
+    
+    // Lower bound and width of each bucket
+    fn bucket_bounds(&self, buckets: usize) -> (f32, f32) {
+        let (min, max) = self.range.unwrap_or_else(|| {
+            let min = self.values.iter().copied().fold(f32::INFINITY, f32::min);
+            let max = self.values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+            (min, max)
+        });
+        
+        // A constant series still gets a bucket of its own
+        let span = if max > min { max - min } else { 1.0 };
+        (min, span / buckets as f32)
+    }
+    
+    // Format a value with a decimal only when buckets are narrow
+    fn format_value(&self, value: f32, precise: bool) -> String {
+        if precise {
+            format!("{:.1}", value)
+        } else {
+            format!("{:.0}", value)
+        }
+    }
+}
+
+impl Widget for Histogram {
+    fn draw(&self, stdout: &mut impl Write) -> io::Result<()> {
+        if self.values.is_empty() {
+            execute!(
+                stdout,
+                MoveTo(self.x, self.y),
+                SetForegroundColor(Color::DarkGrey),
+                crossterm::style::Print("No samples recorded yet."),
+                ResetColor
+            )?;
+            return Ok(());
+        }
+        
+        let mut sorted = self.values.clone();
+        sorted.sort_by(f32::total_cmp);
+        let mean = sorted.iter().sum::<f32>() / sorted.len() as f32;
+        
+        // Percentiles first: a steady 50% and a 0/100 alternation share a mean but not a p10/p90
+        let summary = [
+            ("min", sorted[0]),
+            ("p10", percentile(&sorted, 10.0)),
+            ("p50", percentile(&sorted, 50.0)),
+            ("p90", percentile(&sorted, 90.0)),
+            ("p99", percentile(&sorted, 99.0)),
+            ("max", sorted[sorted.len() - 1]),
+            ("mean", mean),
+        ];
+        execute!(stdout, MoveTo(self.x, self.y))?;
+        let mut used = 0;
+        for (label, value) in summary.iter() {
+            let value = format!("{:.1}{}", value, self.unit);
+            let entry_width = label.len() + 1 + value.chars().count();
+            if used + entry_width > self.width {
+                break;
+            }
+            
+            execute!(
+                stdout,
+                SetForegroundColor(Color::DarkGrey),
+                crossterm::style::Print(format!("{} ", label)),
+                ResetColor,
+                crossterm::style::Print(format!("{}  ", value))
+            )?;
+            used += entry_width + 2;
+        }
+        
+        // One row per bucket below a blank row
+        let buckets = self.height.saturating_sub(2).clamp(1, MAX_BUCKETS);
+        let (min, bucket_width) = self.bucket_bounds(buckets);
+        let mut counts = vec![0usize; buckets];
+        for value in sorted.iter() {
+            let bucket = ((value - min) / bucket_width).floor().max(0.0) as usize;
+            counts[bucket.min(buckets - 1)] += 1;
+        }
+        
+        let precise = bucket_width < 1.0;
+        let labels: Vec<String> = (0..buckets)
+            .map(|i| {
+                let low = min + bucket_width * i as f32;
+                format!(
+                    "{}-{}{}",
+                    self.format_value(low, precise),
+                    self.format_value(low + bucket_width, precise),
+                    self.unit
+                )
+            })
+            .collect();
+        let label_width = labels.iter().map(|label| label.chars().count()).max().unwrap_or(0);
+        let bar_width = self.width.saturating_sub(label_width + 10).max(1);
+        let peak = counts.iter().copied().max().unwrap_or(1).max(1);
+        
+        for (i, (count, label)) in counts.iter().zip(labels.iter()).enumerate() {
+            let share = *count as f32 / sorted.len() as f32 * 100.0;
+            let length = ((*count as f32 / peak as f32) * bar_width as f32).round() as usize;
+            
+            execute!(
+                stdout,
+                MoveTo(self.x, self.y + 2 + i as u16),
+                SetForegroundColor(Color::White),
+                crossterm::style::Print(format!("{:>width$} ", label, width = label_width)),
+                SetForegroundColor(Color::Cyan),
+                crossterm::style::Print("█".repeat(length)),
+                SetForegroundColor(Color::DarkGrey),
+                crossterm::style::Print("░".repeat(bar_width - length)),
+                ResetColor,
+                crossterm::style::Print(format!(" {:5.1}%", share))
+            )?;
+        }
+        
+        Ok(())
+    }
+}
+
+/// One cell of a Heatmap
+pub struct HeatmapCell {
+    pub label: String,
+    pub values: Vec<f32>,  // percentages, oldest first; the last is the current value
+}
+
+/// A grid of cells colored by usage, for showing many cores in little space.
+/// Cells show their value when there is room, and a shade character otherwise;
+/// in history mode each cell becomes a strip of its recent values.
+pub struct Heatmap {
+    x: u16,
+    y: u16,
+    width: usize,
+    height: usize,
+    cells: Vec<HeatmapCell>,
+    history: bool,
+}
+
+// Shade character for a percentage, from idle to saturated
+fn heat_shade(value: f32) -> char {
+    if value < 5.0 {
+        '·'
+    } else if value < 25.0 {
+        '░'
+    } else if value < 50.0 {
+        '▒'
+    } else if value < 75.0 {
+        '▓'
+    } else {
+        '█'
+    }
+}
+
+impl Heatmap {
+    /// Create a heatmap occupying the given area of the screen
+    pub fn new(x: u16, y: u16, width: usize, height: usize, cells: Vec<HeatmapCell>) -> Self {
+        Heatmap {
+            x,
+            y,
+            width,
+            height: height.max(2),
+            cells,
+            history: false,
+        }
+    }
+    
+    /// Show each cell's recent values instead of only the current one
+    pub fn with_history(mut self, history: bool) -> Self {
+        self.history = history;
+        self
+    }
+    
+    // Pick the cell width, the number of cells per row and whether cells show
+    // numbers, so the whole grid fits in the area above the legend row
+    fn layout(&self, grid_width: usize) -> (usize, usize, bool) {
+        let rows = (self.height - 1).max(1);
+        let count = self.cells.len().max(1);
+        let fits = |cell_width: usize| {
+            let per_row = (grid_width / cell_width).max(1);
+            count.div_ceil(per_row) <= rows
+        };
+        
+        if self.history {
+            // Spread the cells over every available row and give each the leftover width
+            let per_row = count.div_ceil(rows);
+            let cell_width = grid_width / per_row;
+            if cell_width >= 3 {
+                return (cell_width, per_row, false);
+            }
+        }
+        
+        let cell_width = [4, 2].iter().copied().find(|width| fits(*width)).unwrap_or(1);
+        (cell_width, (grid_width / cell_width).max(1), cell_width == 4)
+    }
+}
+
+impl Widget for Heatmap {
+    fn draw(&self, stdout: &mut impl Write) -> io::Result<()> {
+        let label_width = self.cells.iter().map(|cell| cell.label.len()).max().unwrap_or(1) + 1;
+        let grid_width = self.width.saturating_sub(label_width + 1).max(1);
+        let (cell_width, per_row, numbers) = self.layout(grid_width);
+        let rows = (self.height - 1).max(1);
+        
+        for (row, chunk) in self.cells.chunks(per_row).take(rows).enumerate() {
+            execute!(
+                stdout,
+                MoveTo(self.x, self.y + row as u16),
+                SetForegroundColor(Color::DarkGrey),
+                crossterm::style::Print(format!("{:>width$}│", chunk[0].label, width = label_width)),
+            )?;
+            
+            for cell in chunk {
+                let current = cell.values.last().copied().unwrap_or(0.0);
+                
+                if numbers {
+                    execute!(
+                        stdout,
+                        SetForegroundColor(usage_color(current)),
+                        crossterm::style::Print(format!("{:>3.0} ", current))
+                    )?;
+                } else if self.history && cell_width >= 3 {
+                    // Newest value on the right, padded when history is short
+                    let strip_width = cell_width - 1;
+                    let start = cell.values.len().saturating_sub(strip_width);
+                    execute!(
+                        stdout,
+                        crossterm::style::Print(" ".repeat(strip_width.saturating_sub(cell.values.len())))
+                    )?;
+                    for value in &cell.values[start..] {
+                        execute!(
+                            stdout,
+                            SetForegroundColor(usage_color(*value)),
+                            crossterm::style::Print(heat_shade(*value))
+                        )?;
+                    }
+                    execute!(stdout, crossterm::style::Print(" "))?;
+                } else {
+                    let gap = " ".repeat(cell_width - 1);
+                    execute!(
+                        stdout,
+                        SetForegroundColor(usage_color(current)),
+                        crossterm::style::Print(format!("{}{}", heat_shade(current), gap))
+                    )?;
+                }
+            }
+            execute!(stdout, ResetColor)?;
+        }
+        
+        // Legend for the shade characters
+        if !numbers {
+            let legend_row = self.y + self.cells.len().div_ceil(per_row).min(rows) as u16;
+            execute!(
+                stdout,
+                MoveTo(self.x + label_width as u16 + 1, legend_row),
+                SetForegroundColor(Color::DarkGrey),
+                crossterm::style::Print("· <5%  ░ <25%  ▒ <50%  ▓ <75%  █ ≥75%"),
+                ResetColor
+            )?;
+        }
+        
+        Ok(())
+    }
+}
+
+/// Horizontal alignment of a table column
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Align {
+    Left,
+    Right,
+}
+
+/// Direction a table is sorted in
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SortOrder {
+    Ascending,
+    Descending,
+}
+
+/// A table column with its width constraints
+pub struct TableColumn {
+    title: String,
+    align: Align,
+    min_width: usize,
+    max_width: usize,
+}
+
+impl TableColumn {
+    /// Create a left-aligned column sized to its content
+    pub fn new(title: &str) -> Self {
+        TableColumn {
+            title: title.to_string(),
+            align: Align::Left,
+            min_width: 1,
+            max_width: usize::MAX,
+        }
+    }
+    
+    /// Set the column's alignment
+    pub fn align(mut self, align: Align) -> Self {
+        self.align = align;
+        self
+    }
+    
+    /// Never shrink the column below `width` characters
+    pub fn min_width(mut self, width: usize) -> Self {
+        self.min_width = width.max(1);
+        self
+    }
+    
+    /// Never grow the column beyond `width` characters
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.max_width = width.max(1);
+        self
+    }
+}
+
+/// A table cell with an optional color
+pub struct TableCell {
+    pub text: String,
+    pub color: Option<Color>,
+    pub sort_key: Option<f64>,  // Sorts numerically by this instead of by text
+}
+
+impl TableCell {
+    /// Create a colored cell
+    pub fn colored(text: String, color: Color) -> Self {
+        TableCell { text, color: Some(color), sort_key: None }
+    }
+    
+    /// Sort the cell by a number (e.g. bytes) rather than by its formatted text
+    pub fn with_sort_key(mut self, key: f64) -> Self {
+        self.sort_key = Some(key);
+        self
+    }
+    
+    // Cells with a sort key order numerically and after cells without one,
+    // which order by text
+    fn sort_cmp(&self, other: &TableCell) -> std::cmp::Ordering {
+        match (self.sort_key, other.sort_key) {
+            (Some(a), Some(b)) => a.total_cmp(&b),
+            (None, None) => self.text.to_lowercase().cmp(&other.text.to_lowercase()),
+            (a, b) => a.is_some().cmp(&b.is_some()),
+        }
+    }
+}
+
+impl From<String> for TableCell {
+    fn from(text: String) -> Self {
+        TableCell { text, color: None, sort_key: None }
+    }
+}
+
+impl From<&str> for TableCell {
+    fn from(text: &str) -> Self {
+        TableCell::from(text.to_string())
+    }
+}
+
+/// A table with column widths fitted to the available space, an optional
+/// header, border, sort column and selected row, scrolling vertically
+/// when there are more rows than fit
+pub struct Table {
+    x: u16,
+    y: u16,
+    width: usize,
+    max_height: usize,
+    columns: Vec<TableColumn>,
+    rows: Vec<Vec<TableCell>>,
+    show_header: bool,
+    bordered: bool,
+    sort: Option<(usize, SortOrder)>,
+    selected: Option<usize>,
+}
+
+// Fit text into a column, truncating with an ellipsis
+fn fit_cell(text: &str, width: usize, align: Align) -> String {
+    let length = text.chars().count();
+    let text = if length > width {
+        let mut truncated: String = text.chars().take(width.saturating_sub(1)).collect();
+        truncated.push('…');
+        truncated
+    } else {
+        text.to_string()
+    };
+    
+    match align {
+        Align::Left => format!("{:<width$}", text, width = width),
+        Align::Right => format!("{:>width$}", text, width = width),
+    }
+}
+
+impl Table {
+    /// Create a table at the given position, at most `width` columns wide
+    /// and `max_height` rows tall
+    pub fn new(x: u16, y: u16, width: usize, max_height: usize, columns: Vec<TableColumn>) -> Self {
+        Table {
+            x,
+            y,
+            width,
+            max_height: max_height.max(1),
+            columns,
+            rows: Vec::new(),
+            show_header: true,
+            bordered: false,
+            sort: None,
+            selected: None,
+        }
+    }
+    
+    /// Add a row of cells
+    pub fn row(mut self, cells: Vec<TableCell>) -> Self {
+        self.rows.push(cells);
+        self
+    }
+    
+    /// Hide the header row
+    pub fn without_header(mut self) -> Self {
+        self.show_header = false;
+        self
+    }
+    
+    /// Draw box-drawing borders around the table and between columns
+    pub fn bordered(mut self) -> Self {
+        self.bordered = true;
+        self
+    }
+    
+    /// Sort the rows by a column and mark it in the header
+    pub fn sorted_by(mut self, column: usize, order: SortOrder) -> Self {
+        self.sort = Some((column, order));
+        self
+    }
+    
+    /// Highlight a row, scrolling so that it is visible
+    pub fn with_selected(mut self, row: usize) -> Self {
+        self.selected = Some(row);
+        self
+    }
+    
+    // Header text of a column, with the sort indicator where it applies
+    fn header_text(&self, index: usize) -> String {
+        match self.sort {
+            Some((column, SortOrder::Ascending)) if column == index => format!("{} ▲", self.columns[index].title),
+            Some((column, SortOrder::Descending)) if column == index => format!("{} ▼", self.columns[index].title),
+            _ => self.columns[index].title.clone(),
+        }
+    }
+    
+    // Rows in display order: sorted by the sort column, if any, keeping the
+    // order rows were added in among equal cells
+    fn sorted_rows(&self) -> Vec<&Vec<TableCell>> {
+        let mut rows: Vec<&Vec<TableCell>> = self.rows.iter().collect();
+        if let Some((column, order)) = self.sort {
+            rows.sort_by(|a, b| {
+                let ordering = match (a.get(column), b.get(column)) {
+                    (Some(a), Some(b)) => a.sort_cmp(b),
+                    (a, b) => a.is_some().cmp(&b.is_some()),
+                };
+                match order {
+                    SortOrder::Ascending => ordering,
+                    SortOrder::Descending => ordering.reverse(),
+                }
+            });
+        }
+        rows
+    }
+    
+    // Width of every column: the widest cell within the column's limits,
+    // shrinking the widest columns until the table fits
+    fn column_widths(&self) -> Vec<usize> {
+        let mut widths: Vec<usize> = self.columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let header = if self.show_header { self.header_text(i).chars().count() } else { 0 };
+                let content = self.rows
+                    .iter()
+                    .filter_map(|row| row.get(i))
+                    .map(|cell| cell.text.chars().count())
+                    .max()
+                    .unwrap_or(0);
+                header.max(content).clamp(column.min_width, column.max_width.max(column.min_width))
+            })
+            .collect();
+        
+        // Each column has a separator and a space of padding on either side
+        let chrome = if self.bordered { self.columns.len() * 3 + 1 } else { self.columns.len().saturating_sub(1) * 2 };
+        let available = self.width.saturating_sub(chrome);
+        
+        while widths.iter().sum::<usize>() > available {
+            let widest = widths
+                .iter()
+                .enumerate()
+                .filter(|(i, width)| **width > self.columns[*i].min_width)
+                .max_by_key(|(_, width)| **width)
+                .map(|(i, _)| i);
+            match widest {
+                Some(i) => widths[i] -= 1,
+                None => break,
+            }
+        }
+        
+        widths
+    }
+    
+    // Rows taken by the border and header
+    fn chrome_rows(&self) -> (usize, usize) {
+        let above = match (self.bordered, self.show_header) {
+            (true, true) => 3,
+            (true, false) | (false, true) => 1,
+            (false, false) => 0,
+        };
+        let below = if self.bordered { 1 } else { 0 };
+        (above, below)
+    }
+    
+    // Number of data rows that fit, and the first one to show
+    fn visible_window(&self) -> (usize, usize) {
+        let (above, below) = self.chrome_rows();
+        let mut visible = self.max_height.saturating_sub(above + below).max(1);
+        
+        // Unbordered tables use a footer row to say that rows are hidden
+        if !self.bordered && self.rows.len() > visible {
+            visible = visible.saturating_sub(1).max(1);
+        }
+        
+        // Scroll just far enough to keep the selected row in view
+        let first = match self.selected {
+            Some(selected) if selected >= visible => (selected + 1 - visible).min(self.rows.len().saturating_sub(visible)),
+            _ => 0,
+        };
+        (visible, first)
+    }
+    
+    /// Number of terminal rows the table occupies when drawn
+    pub fn height(&self) -> usize {
+        let (above, below) = self.chrome_rows();
+        let (visible, _) = self.visible_window();
+        let footer = if !self.bordered && self.rows.len() > visible { 1 } else { 0 };
+        above + self.rows.len().min(visible) + below + footer
+    }
+    
+    // Draw a horizontal border line such as ├───┼───┤
+    fn draw_rule(&self, stdout: &mut impl Write, row: u16, widths: &[usize], chars: [char; 3], label: &str) -> io::Result<()> {
+        let mut line = String::new();
+        line.push(chars[0]);
+        let segments: Vec<String> = widths.iter().map(|width| "─".repeat(width + 2)).collect();
+        line.push_str(&segments.join(&chars[1].to_string()));
+        line.push(chars[2]);
+        
+        // Overlay a label such as a row range onto the right of the rule
+        if !label.is_empty() {
+            let length = line.chars().count();
+            let label_length = label.chars().count();
+            if label_length + 4 < length {
+                let keep: String = line.chars().take(length - label_length - 2).collect();
+                line = format!("{}{}─{}", keep, label, chars[2]);
+            }
+        }
+        
+        execute!(stdout, MoveTo(self.x, row), crossterm::style::Print(line))
+    }
+}
+
+impl Widget for Table {
+    fn draw(&self, stdout: &mut impl Write) -> io::Result<()> {
+        let widths = self.column_widths();
+        let (visible, first) = self.visible_window();
+        let separator = if self.bordered { " │ " } else { "  " };
+        let mut row = self.y;
+        
+        if self.bordered {
+            self.draw_rule(stdout, row, &widths, ['┌', '┬', '┐'], "")?;
+            row += 1;
+        }
+        
+        if self.show_header {
+            let header: Vec<String> = widths
+                .iter()
+                .enumerate()
+                .map(|(i, width)| fit_cell(&self.header_text(i), *width, self.columns[i].align))
+                .collect();
+            let (left, right) = if self.bordered { ("│ ", " │") } else { ("", "") };
+            execute!(
+                stdout,
+                MoveTo(self.x, row),
+                crossterm::style::Print(left),
+                SetForegroundColor(Color::White),
+                SetAttribute(Attribute::Bold),
+                crossterm::style::Print(header.join(separator)),
+                SetAttribute(Attribute::Reset),
+                ResetColor,
+                crossterm::style::Print(right)
+            )?;
+            row += 1;
+            
+            if self.bordered {
+                self.draw_rule(stdout, row, &widths, ['├', '┼', '┤'], "")?;
+                row += 1;
+            }
+        }
+        
+        for (index, cells) in self.sorted_rows().into_iter().enumerate().skip(first).take(visible) {
+            let is_selected = self.selected == Some(index);
+            execute!(stdout, MoveTo(self.x, row))?;
+            if self.bordered {
+                execute!(stdout, crossterm::style::Print("│ "))?;
+            }
+            if is_selected {
+                execute!(stdout, SetAttribute(Attribute::Reverse))?;
+            }
+            
+            for (i, width) in widths.iter().enumerate() {
+                if i > 0 {
+                    execute!(stdout, crossterm::style::Print(separator))?;
+                }
+                let cell = cells.get(i);
+                let text = fit_cell(cell.map(|cell| cell.text.as_str()).unwrap_or(""), *width, self.columns[i].align);
+                match cell.and_then(|cell| cell.color) {
+                    Some(color) => execute!(
+                        stdout,
+                        SetForegroundColor(color),
+                        crossterm::style::Print(text),
+                        SetForegroundColor(Color::Reset)
+                    )?,
+                    None => execute!(stdout, crossterm::style::Print(text))?,
+                }
+            }
+            
+            if is_selected {
+                execute!(stdout, SetAttribute(Attribute::Reset))?;
+            }
+            if self.bordered {
+                execute!(stdout, crossterm::style::Print(" │"))?;
+            }
+            row += 1;
+        }
+        
+        // Say which rows are shown when some are scrolled out of view
+        let range = if self.rows.len() > visible {
+            format!(" {}-{} of {} ", first + 1, (first + visible).min(self.rows.len()), self.rows.len())
+        } else {
+            String::new()
+        };
+        
+        if self.bordered {
+            self.draw_rule(stdout, row, &widths, ['└', '┴', '┘'], &range)?;
+        } else if !range.is_empty() {
+            execute!(
+                stdout,
+                MoveTo(self.x, row),
+                SetForegroundColor(Color::DarkGrey),
+                crossterm::style::Print(range.trim()),
+                ResetColor
+            )?;
+        }
+        
+        Ok(())
+    }
+}
+
 /// A simple text widget for displaying information
+#[allow(dead_code)]
 pub struct TextWidget {
     lines: Vec<String>,
     color: Option<Color>,
 }
 
+#[allow(dead_code)]
 impl TextWidget {
     /// Create a new text widget with the given lines
     pub fn new(text: &str) -> Self {
@@ -148,4 +1494,28 @@ impl Widget for TextWidget {
         
         Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sorted_texts(table: &Table, column: usize) -> Vec<String> {
+        table.sorted_rows().iter().map(|row| row[column].text.clone()).collect()
+    }
+
+    #[test]
+    fn table_sorts_numeric_cells_by_key_and_text_cells_by_text() {
+        let table = |column: usize, order: SortOrder| {
+            Table::new(0, 0, 40, 10, vec![TableColumn::new("NAME"), TableColumn::new("MEMORY")])
+                .sorted_by(column, order)
+                .row(vec![TableCell::from("web"), TableCell::from("900 MB").with_sort_key(900.0)])
+                .row(vec![TableCell::from("DB"), TableCell::from("n/a")])
+                .row(vec![TableCell::from("cache"), TableCell::from("1200 MB").with_sort_key(1200.0)])
+        };
+
+        assert_eq!(sorted_texts(&table(0, SortOrder::Ascending), 0), ["cache", "DB", "web"]);
+        assert_eq!(sorted_texts(&table(1, SortOrder::Descending), 1), ["1200 MB", "900 MB", "n/a"]);
+        assert_eq!(sorted_texts(&table(1, SortOrder::Ascending), 1), ["n/a", "900 MB", "1200 MB"]);
+    }
+}